@@ -5,7 +5,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         let mut itr = line.chars().filter(char::is_ascii_digit);
 
         let first = itr.next().unwrap();
-        let last = itr.last().unwrap_or(first);
+        let last = itr.next_back().unwrap_or(first);
 
         c += format!("{first}{last}").parse::<u32>().unwrap();
     }
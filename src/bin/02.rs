@@ -1,31 +1,30 @@
-use std::str::FromStr;
-
-use aoc::parsers::to_vec;
+use aoc::parsers::combinator::{
+    key_value, lines, map, one_of, pair, parse_all, preceded, separated, tag, terminated, unsigned,
+    word, Input, PResult, Parser,
+};
 
 struct Game {
     id: u32,
     balls: Vec<(u32, String)>,
 }
 
-impl FromStr for Game {
-    type Err = String;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rest = s.strip_prefix("Game ").unwrap();
-        let (id, rest) = rest.split_once(':').unwrap();
-        let id = id.parse().unwrap();
+fn game(input: Input) -> PResult<Game> {
+    let ball = pair(terminated(unsigned, tag(" ")), map(word, String::from));
+    let parser = key_value(
+        preceded(tag("Game "), unsigned),
+        tag(": "),
+        separated(ball, one_of(&[", ", "; "])),
+    );
 
-        let balls = rest
-            .split([',', ';'])
-            .map(|x| x.strip_prefix(' ').unwrap().split_once(' ').unwrap())
-            .map(|(n, c)| (n.parse::<u32>().unwrap(), c.to_string()))
-            .collect();
+    map(parser, |(id, balls)| Game { id, balls }).parse(input)
+}
 
-        Ok(Game { id, balls })
-    }
+fn parse_input(input: &str) -> Vec<Game> {
+    parse_all(input, lines(game)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let games: Vec<Game> = to_vec(input, '\n');
+    let games = parse_input(input);
     let rules = [(12, "red"), (13, "green"), (14, "blue")];
 
     Some(
@@ -42,7 +41,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let games: Vec<Game> = to_vec(input, '\n');
+    let games = parse_input(input);
 
     let mut total_power = 0;
 
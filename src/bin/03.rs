@@ -1,3 +1,5 @@
+use aoc::grid::Grid;
+
 struct MappedPart {
     number: u32,
     line: usize,
@@ -41,21 +43,19 @@ fn build_map(input: &str) -> Vec<MappedPart> {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let numbers = build_map(input);
+    let schematic: Grid<char> = input.parse().ok()?;
 
     let mut part_numbers = 0;
 
-    for (idl, line) in input.lines().enumerate() {
-        for (idc, _) in line
-            .chars()
-            .enumerate()
-            .filter(|(_, c)| !(*c == '.' || c.is_ascii_digit()))
-        {
-            part_numbers += numbers
-                .iter()
-                .filter(|mp| mp.is_adjacent(idl, idc))
-                .map(|mp| mp.number)
-                .sum::<u32>();
-        }
+    for ((idl, idc), _) in schematic
+        .iter()
+        .filter(|(_, c)| !(**c == '.' || c.is_ascii_digit()))
+    {
+        part_numbers += numbers
+            .iter()
+            .filter(|mp| mp.is_adjacent(idl, idc))
+            .map(|mp| mp.number)
+            .sum::<u32>();
     }
 
     Some(part_numbers)
@@ -63,19 +63,18 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let numbers = build_map(input);
+    let schematic: Grid<char> = input.parse().ok()?;
 
     let mut gear_ratios = 0;
 
-    for (idl, line) in input.lines().enumerate() {
-        for (idc, _) in line.chars().enumerate().filter(|(_, c)| *c == '*') {
-            let touching: Vec<_> = numbers
-                .iter()
-                .filter(|mp| mp.is_adjacent(idl, idc))
-                .map(|mp| mp.number)
-                .collect();
-            if touching.len() == 2 {
-                gear_ratios += touching.iter().product::<u32>();
-            }
+    for ((idl, idc), _) in schematic.iter().filter(|(_, c)| **c == '*') {
+        let touching: Vec<_> = numbers
+            .iter()
+            .filter(|mp| mp.is_adjacent(idl, idc))
+            .map(|mp| mp.number)
+            .collect();
+        if touching.len() == 2 {
+            gear_ratios += touching.iter().product::<u32>();
         }
     }
 
@@ -1,24 +1,11 @@
-use std::str::FromStr;
+use aoc::parsers::{try_to_vec, Empty};
 
-use aoc::parsers::to_vec;
-
-struct ParseCardError;
-
-struct Card {
-    winning: Vec<u32>,
-    numbers: Vec<u32>,
-}
-
-impl FromStr for Card {
-    type Err = ParseCardError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, rest) = s.split_once(": ").unwrap();
-        let (win, my) = rest.split_once(" | ").unwrap();
-
-        let winning: Vec<u32> = to_vec(win, ' ');
-        let numbers: Vec<u32> = to_vec(my, ' ');
-
-        Ok(Card { winning, numbers })
+// numbers are aligned with extra spaces
+aoc::record! {
+    #[format("Card {_}: {winning} | {numbers}")]
+    struct Card {
+        winning: Vec<u32> => |s| try_to_vec(s, ' ', Empty::Skip),
+        numbers: Vec<u32> => |s| try_to_vec(s, ' ', Empty::Skip),
     }
 }
 
@@ -40,18 +27,17 @@ impl Card {
     }
 }
 
+fn parse_input(input: &str) -> Vec<Card> {
+    try_to_vec(input, '\n', Empty::Skip).unwrap_or_else(|e| panic!("{e}"))
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .filter_map(|l| l.parse::<Card>().ok())
-            .map(|g| g.score())
-            .sum(),
-    )
+    let cards = parse_input(input);
+    Some(cards.iter().map(|g| g.score()).sum())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let cards: Vec<Card> = to_vec(input, '\n');
+    let cards = parse_input(input);
     let mut multiples = vec![1; cards.len()];
 
     for (i, card) in cards.iter().enumerate() {
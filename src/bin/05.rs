@@ -1,33 +1,11 @@
-use std::{
-    cmp::{max, min},
-    fmt::Debug,
-    ops::RangeInclusive,
-    str::FromStr,
-};
+use std::{cmp::min, ops::Range, str::FromStr};
 
-use aoc::parsers::to_vec;
-
-trait RangeInclusiveExt {
-    fn overlaps(&self, other: &Self) -> bool;
-}
-
-impl<T> RangeInclusiveExt for RangeInclusive<T>
-where
-    T: PartialOrd,
-{
-    fn overlaps(&self, other: &Self) -> bool {
-        self.contains(other.start()) || self.contains(other.end())
-    }
-}
-
-fn build_range(start: u64, range: u64) -> RangeInclusive<u64> {
-    start..=(start + range - 1)
-}
+use aoc::{parsers::to_vec, ranges::RangeSet};
 
 #[derive(Debug)]
 struct Mapping {
-    pub source: RangeInclusive<u64>,
-    pub destination: RangeInclusive<u64>,
+    pub source: Range<u64>,
+    pub destination: u64,
 }
 
 struct ParseMappingError;
@@ -38,43 +16,16 @@ impl FromStr for Mapping {
         let nums: Vec<u64> = to_vec(s, ' ');
 
         Ok(Self {
-            destination: build_range(nums[0], nums[2]),
-            source: build_range(nums[1], nums[2]),
+            destination: nums[0],
+            source: nums[1]..nums[1] + nums[2],
         })
     }
 }
 
 impl Mapping {
     fn map(&self, n: u64) -> u64 {
-        let shift = n - self.source.start();
-        self.destination.start() + shift
-    }
-
-    fn map_range(&self, r: RangeInclusive<u64>) -> RangeInclusive<u64> {
-        self.map(*r.start())..=(self.map(*r.end()))
-    }
-
-    fn split_range(&self, r: RangeInclusive<u64>) -> [Option<RangeInclusive<u64>>; 3] {
-        let mut fences = [
-            *r.start(),
-            *r.end() + 1,
-            max(*self.source.start(), *r.start()),
-            min(*self.source.end() + 1, *r.end() + 1),
-        ];
-
-        fences.sort();
-
-        const ARRAY_REPEAT_VALUE: Option<RangeInclusive<u64>> = None;
-        let mut v = [ARRAY_REPEAT_VALUE; 3];
-
-        for i in 0..3 {
-            let f = fences[i];
-            let nf = fences[i + 1];
-            if f != nf {
-                v[i] = Some(f..=(nf - 1))
-            }
-        }
-        v
+        let shift = n - self.source.start;
+        self.destination + shift
     }
 }
 
@@ -121,37 +72,25 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     let (first, rest) = input.split_once("\n\n").unwrap();
     let maps = parse_map(rest);
-    let mut seeds_ranges: Vec<_> = to_vec::<u64, _>(first.strip_prefix("seeds: ").unwrap(), ' ')
+    let mut seeds: RangeSet<u64> = to_vec::<u64, _>(first.strip_prefix("seeds: ").unwrap(), ' ')
         .chunks(2)
-        .map(|x| build_range(x[0], x[1]))
+        .map(|x| x[0]..x[0] + x[1])
         .collect();
 
     for mapping in maps.iter() {
-        let mut new_ranges = Vec::new();
-
-        'queue: while let Some(rng) = seeds_ranges.pop() {
-            for map in mapping {
-                if map.source.overlaps(&rng) {
-                    let [pre, to_map, post] = map.split_range(rng);
-                    new_ranges.push(map.map_range(to_map.unwrap()));
-                    for r in [pre, post].into_iter().flatten() {
-                        seeds_ranges.push(r);
-                    }
-                    continue 'queue;
-                }
-            }
+        let mut mapped = RangeSet::new();
 
-            new_ranges.push(rng);
+        for map in mapping {
+            let source = RangeSet::from(map.source.clone());
+            let hit = seeds.intersection(&source);
+            mapped = mapped.union(&hit.shift(map.source.start, map.destination));
+            seeds = seeds.difference(&source);
         }
 
-        seeds_ranges = new_ranges;
+        seeds = seeds.union(&mapped);
     }
 
-    seeds_ranges
-        .iter()
-        .map(RangeInclusive::start)
-        .min()
-        .copied()
+    seeds.min()
 }
 
 aoc::solution!(5);
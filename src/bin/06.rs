@@ -1,48 +1,53 @@
+use aoc::parsers::combinator::{numbers, pair, parse_all, section, tag, terminated};
+
 fn win_options((time, distance): (u64, u64)) -> u64 {
-    let discriminant = ((time.pow(2) - 4 * distance) as f64).sqrt();
+    let wins = |hold: u64| hold * (time - hold) > distance;
+    let discriminant = (time * time).saturating_sub(4 * distance);
 
-    let first_zero = (time as f64 - discriminant) / 2.0;
-    let mut second_zero = (time as f64 + discriminant) / 2.0;
+    // the lower root rounded, then nudged to the first hold time that wins
+    let mut first = (time - aoc::math::isqrt(discriminant)) / 2;
+    while first <= time / 2 && !wins(first) {
+        first += 1;
+    }
+    while first > 0 && wins(first - 1) {
+        first -= 1;
+    }
 
-    if second_zero.fract() == 0.0 {
-        second_zero -= 1.0
+    if first > time / 2 {
+        return 0;
     }
+    // holding for `hold` and for `time - hold` travel the same distance
+    time - 2 * first + 1
+}
+
+fn parse_input(input: &str) -> (Vec<u64>, Vec<u64>) {
+    let parser = pair(
+        terminated(section("Time", numbers), tag("\n")),
+        section("Distance", numbers),
+    );
+    parse_all(input, parser).unwrap_or_else(|e| panic!("{e}"))
+}
 
-    second_zero as u64 - first_zero as u64
+// the numbers were meant to be read without the spaces in between
+fn join_digits(numbers: &[u64]) -> Option<u64> {
+    numbers
+        .iter()
+        .map(u64::to_string)
+        .collect::<String>()
+        .parse()
+        .ok()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let [time, distance] = input
-        .lines()
-        .map(|l| {
-            l.split_whitespace()
-                .skip(1)
-                .filter_map(|n| n.parse().ok())
-                .collect::<Vec<u64>>()
-        })
-        .collect::<Vec<_>>()
-        .try_into()
-        .ok()?;
+    let (time, distance) = parse_input(input);
 
     Some(time.into_iter().zip(distance).map(win_options).product())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let [time, distance] = input
-        .lines()
-        .filter_map(|l| {
-            l.split_whitespace()
-                .skip(1)
-                .flat_map(|x| x.chars())
-                .collect::<String>()
-                .parse()
-                .ok()
-        })
-        .collect::<Vec<_>>()
-        .try_into()
-        .ok()?;
+    let (time, distance) = parse_input(input);
 
-    Some(win_options((time, distance)))
+    Some(win_options((join_digits(&time)?, join_digits(&distance)?)))
 }
 
 aoc::solution!(6);
@@ -141,7 +141,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         .map(|(f, s)| (f.parse::<Hand>().unwrap(), s.parse::<u32>().unwrap()))
         .collect::<Vec<_>>();
 
-    v.sort_by(|f, o| f.0.cmp(&o.0));
+    v.sort_by_key(|x| x.0);
 
     Some(
         v.into_iter()
@@ -159,7 +159,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         .map(|(f, s)| (f.parse::<Hand>().unwrap(), s.parse::<u32>().unwrap()))
         .collect::<Vec<_>>();
 
-    v.sort_by(|f, o| f.0.cmp(&o.0));
+    v.sort_by_key(|x| x.0);
 
     Some(
         v.into_iter()
@@ -1,19 +1,26 @@
 use std::collections::HashSet;
 
+use aoc::grid::Grid;
+
 fn parse_input(input: &str) -> (Vec<(usize, usize)>, HashSet<usize>, HashSet<usize>) {
-    let mut galaxies = Vec::new();
-    let mut rows = HashSet::from_iter(0..input.lines().count());
-    let mut columns = HashSet::from_iter(0..input.lines().next().unwrap().len());
+    let grid: Grid<char> = input.parse().unwrap();
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            if c == '#' {
-                galaxies.push((y, x));
-                rows.remove(&y);
-                columns.remove(&x);
-            }
-        }
-    }
+    let galaxies = grid
+        .iter()
+        .filter(|(_, c)| **c == '#')
+        .map(|(p, _)| p)
+        .collect();
+    let rows = grid
+        .rows()
+        .enumerate()
+        .filter(|(_, row)| !row.contains(&'#'))
+        .map(|(y, _)| y)
+        .collect();
+    let columns = grid
+        .columns()
+        .enumerate()
+        .filter_map(|(x, mut column)| (!column.any(|c| *c == '#')).then_some(x))
+        .collect();
 
     (galaxies, rows, columns)
 }
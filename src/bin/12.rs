@@ -1,4 +1,6 @@
-use std::{collections::HashMap, iter::once};
+use std::iter::once;
+
+use aoc::collections::FastHashMap;
 
 fn is_valid(sequence: &[char], ptr: usize, group: usize) -> bool {
     let edges_front = *once(&'.').chain(sequence.iter()).nth(ptr).unwrap() != '#';
@@ -15,7 +17,7 @@ fn is_valid(sequence: &[char], ptr: usize, group: usize) -> bool {
 }
 
 fn count(
-    memo: &mut HashMap<(usize, usize), usize>,
+    memo: &mut FastHashMap<(usize, usize), usize>,
     sequence: &[char],
     groups: &[usize],
     ptr: usize,
@@ -76,7 +78,7 @@ pub fn part_one(input: &str) -> Option<usize> {
         input
             .lines()
             .map(parse_line)
-            .map(|(sequence, groups)| count(&mut HashMap::new(), &sequence, &groups, 0))
+            .map(|(sequence, groups)| count(&mut FastHashMap::default(), &sequence, &groups, 0))
             .sum(),
     )
 }
@@ -87,7 +89,7 @@ pub fn part_two(input: &str) -> Option<usize> {
             .lines()
             .map(parse_line)
             .map(|(sequence, groups)| unfold(sequence, groups, 5))
-            .map(|(sequence, groups)| count(&mut HashMap::new(), &sequence, &groups, 0))
+            .map(|(sequence, groups)| count(&mut FastHashMap::default(), &sequence, &groups, 0))
             .sum(),
     )
 }
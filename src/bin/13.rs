@@ -1,9 +1,11 @@
-fn mirror_h(shape: &[Vec<char>], smudges: usize) -> Option<usize> {
-    (1..shape.len()).find(|&i| {
+use aoc::grid::Grid;
+
+fn mirror_h(shape: &Grid<char>, smudges: usize) -> Option<usize> {
+    (1..shape.height()).find(|&i| {
         shape
-            .iter()
+            .rows()
             .skip(i)
-            .zip(shape.iter().take(i).rev())
+            .zip(shape.rows().take(i).rev())
             .map(|(x, y)| {
                 x.iter()
                     .zip(y.iter())
@@ -15,19 +17,19 @@ fn mirror_h(shape: &[Vec<char>], smudges: usize) -> Option<usize> {
     })
 }
 
-fn mirror_v(shape: &[Vec<char>], smudges: usize) -> Option<usize> {
-    let shape: Vec<Vec<char>> = (0..shape[0].len())
-        .map(|col| (0..shape.len()).map(|row| shape[row][col]).collect())
-        .collect();
-
-    mirror_h(&shape, smudges)
+fn mirror_v(shape: &Grid<char>, smudges: usize) -> Option<usize> {
+    mirror_h(&shape.transposed(), smudges)
 }
 
 fn solve(input: &str, smudges: usize) -> usize {
     input
         .split("\n\n")
-        .map(|x| x.lines().map(|line| line.chars().collect()).collect())
-        .map(|shape: Vec<Vec<char>>| {
+        .enumerate()
+        .map(|(i, x)| {
+            x.parse()
+                .unwrap_or_else(|e| panic!("invalid pattern {}: {e}", i + 1))
+        })
+        .map(|shape: Grid<char>| {
             mirror_v(&shape, smudges).unwrap_or_default()
                 + mirror_h(&shape, smudges).unwrap_or_default() * 100
         })
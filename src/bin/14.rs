@@ -1,4 +1,4 @@
-use std::{collections::HashMap, iter::once};
+use aoc::{cycle, grid::Grid};
 
 enum Tilt {
     North,
@@ -7,27 +7,18 @@ enum Tilt {
     East,
 }
 
-fn get_load(floor: &[Vec<char>]) -> usize {
+fn get_load(floor: &Grid<char>) -> usize {
     floor
         .iter()
-        .enumerate()
-        .flat_map(|(i, x)| x.iter().map(move |y| (i, y)))
-        .filter(|x| *x.1 == 'O')
-        .map(|x| floor.len() - x.0)
+        .filter(|(_, c)| **c == 'O')
+        .map(|((y, _), _)| floor.height() - y)
         .sum()
 }
 
-fn swap<T: Copy>(floor: &mut [Vec<T>], from: (usize, usize), to: (usize, usize)) {
-    let a = floor[from.0][from.1];
-    let b = floor[to.0][to.1];
-    floor[from.0][from.1] = b;
-    floor[to.0][to.1] = a;
-}
-
-fn tilt(floor: &mut [Vec<char>], tilt: Tilt) {
+fn tilt(floor: &mut Grid<char>, tilt: Tilt) {
     let (inner, outer) = match tilt {
-        Tilt::North | Tilt::South => (floor[0].len(), floor.len()),
-        Tilt::West | Tilt::East => (floor.len(), floor[0].len()),
+        Tilt::North | Tilt::South => (floor.width(), floor.height()),
+        Tilt::West | Tilt::East => (floor.height(), floor.width()),
     };
     let inx_n = |(i, j)| (j, i);
     let inx_s = |(i, j)| (inner - 1 - j, i);
@@ -42,9 +33,9 @@ fn tilt(floor: &mut [Vec<char>], tilt: Tilt) {
                 Tilt::East => (inx_e((i, j)), inx_e((i, ptr))),
                 Tilt::West => (inx_w((i, j)), inx_w((i, ptr))),
             };
-            match floor[ii][jj] {
+            match floor[(ii, jj)] {
                 'O' => {
-                    swap(floor, (ii, jj), (pi, pj));
+                    floor.swap((ii, jj), (pi, pj));
                     ptr += 1;
                 }
                 '#' => ptr = j + 1,
@@ -54,7 +45,7 @@ fn tilt(floor: &mut [Vec<char>], tilt: Tilt) {
     }
 }
 
-fn tilt_cycle(floor: &mut [Vec<char>]) {
+fn tilt_cycle(floor: &mut Grid<char>) {
     use Tilt::*;
     tilt(floor, North);
     tilt(floor, West);
@@ -63,32 +54,22 @@ fn tilt_cycle(floor: &mut [Vec<char>]) {
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let mut f: Vec<Vec<_>> = input.lines().map(|x| x.chars().collect()).collect();
+    let mut f: Grid<char> = input.parse().ok()?;
     tilt(&mut f, Tilt::North);
     Some(get_load(&f))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut f: Vec<Vec<_>> = input.lines().map(|x| x.chars().collect()).collect();
-    let mut memo: HashMap<String, usize> = HashMap::new();
-
-    for i in 1.. {
-        tilt_cycle(&mut f);
-        let repr = f
-            .iter()
-            .flat_map(|x| x.iter().chain(once(&'\n')))
-            .collect::<String>();
-
-        if let Some(ii) = memo.insert(repr, i) {
-            let m = i - ii;
-            let shift = (1_000_000_000 - ii) % m;
-
-            for _ in 0..shift {
-                tilt_cycle(&mut f);
-            }
-            break;
-        }
-    }
+    let f: Grid<char> = input.parse().ok()?;
+    let f = cycle::nth(
+        f,
+        |x| {
+            let mut next = x.clone();
+            tilt_cycle(&mut next);
+            next
+        },
+        1_000_000_000,
+    );
 
     Some(get_load(&f))
 }
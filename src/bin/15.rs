@@ -16,7 +16,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     let mut map = vec![Vec::<(&str, usize)>::new(); 256];
 
     for instruction in input.lines().flat_map(|x| x.split(',')) {
-        let (label, n) = instruction.split_once(|x| x == '-' || x == '=').unwrap();
+        let (label, n) = instruction.split_once(['-', '=']).unwrap();
         let hash = hash(label);
         let indexed_map = map.get_mut(hash).unwrap();
 
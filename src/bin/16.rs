@@ -1,24 +1,10 @@
-use std::collections::HashSet;
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+use std::iter::once;
 
-impl Direction {
-    fn next(&self, (y, x): (usize, usize)) -> (usize, usize) {
-        use Direction::*;
-        match self {
-            Up => (y.checked_sub(1).unwrap_or(usize::MAX), x),
-            Down => (y + 1, x),
-            Left => (y, x.checked_sub(1).unwrap_or(usize::MAX)),
-            Right => (y, x + 1),
-        }
-    }
-}
+use aoc::{
+    collections::{BitGrid, BitSet},
+    geom::Direction,
+    grid::Grid,
+};
 
 #[derive(Debug, Clone, Copy)]
 enum Mirror {
@@ -79,56 +65,49 @@ impl Mirror {
     }
 }
 
-fn solve_with_start(layout: &[Vec<Option<Mirror>>], start: (usize, usize, Direction)) -> usize {
+fn solve_with_start(layout: &Grid<Option<Mirror>>, start: (usize, usize, Direction)) -> usize {
+    let (h, w) = (layout.height(), layout.width());
     let mut queue = vec![start];
-    let mut visited = HashSet::new();
+    // one bit for every position and direction
+    let mut visited = BitSet::with_capacity(h * w * 4);
+    let mut energized = BitGrid::new(h, w);
 
     while let Some((y, x, d)) = queue.pop() {
-        let point = layout.get(y).and_then(|row| row.get(x));
-
-        if point.is_none() {
-            continue;
-        }
-
-        if !visited.insert((y, x, d)) {
+        if !visited.insert((y * w + x) * 4 + d as usize) {
             continue;
         }
+        energized.insert((y, x));
 
-        if let Some(Some(m)) = point {
-            let (new_d, opt_new_d) = m.bounce(&d);
-            let (ny, nx) = new_d.next((y, x));
-            queue.push((ny, nx, new_d));
+        let (new_d, opt_new_d) = match layout[(y, x)] {
+            Some(m) => m.bounce(&d),
+            None => (d, None),
+        };
 
-            if let Some(od) = opt_new_d {
-                let (ony, onx) = od.next((y, x));
-                queue.push((ony, onx, od));
+        for nd in once(new_d).chain(opt_new_d) {
+            if let Some((ny, nx)) = layout.offset((y, x), nd.into()) {
+                queue.push((ny, nx, nd));
             }
-        } else {
-            let (ny, nx) = d.next((y, x));
-            queue.push((ny, nx, d));
         }
     }
 
-    HashSet::<(usize, usize)>::from_iter(visited.into_iter().map(|(y, x, _)| (y, x))).len()
+    energized.len()
+}
+
+fn parse_input(input: &str) -> Option<Grid<Option<Mirror>>> {
+    Grid::parse_with(input, |x| (x as u8).try_into().ok()).ok()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let layout: Vec<Vec<_>> = input
-        .lines()
-        .map(|x| x.as_bytes().iter().map(|&x| x.try_into().ok()).collect())
-        .collect();
+    let layout = parse_input(input)?;
 
     Some(solve_with_start(&layout, (0, 0, Direction::Right)))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let layout: Vec<Vec<_>> = input
-        .lines()
-        .map(|x| x.as_bytes().iter().map(|&x| x.try_into().ok()).collect())
-        .collect();
+    let layout = parse_input(input)?;
 
-    let h = layout.len();
-    let w = layout[0].len();
+    let h = layout.height();
+    let w = layout.width();
 
     let mut scores = Vec::with_capacity(2 * (h + w) + 1);
 
@@ -1,107 +1,70 @@
-use std::str::FromStr;
-
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug)]
-struct ParseDirectionError;
-
-impl FromStr for Direction {
-    type Err = ParseDirectionError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use Direction::*;
-
-        Ok(match s {
-            "U" | "3" => Up,
-            "D" | "1" => Down,
-            "L" | "2" => Left,
-            "R" | "0" => Right,
-            _ => return Err(ParseDirectionError),
-        })
+use aoc::{
+    geom::{polygon, Direction, Point2},
+    parsers::try_lines,
+};
+
+aoc::record! {
+    #[format("{direction} {length} (#{color})")]
+    struct Instruction {
+        direction: Direction,
+        length: i128,
+        color: (Direction, i128) => |s| parse_color(s).ok_or(()),
     }
 }
 
-impl From<Direction> for (isize, isize) {
-    fn from(value: Direction) -> Self {
-        use Direction::*;
-        match value {
-            Up => (-1, 0),
-            Down => (1, 0),
-            Left => (0, -1),
-            Right => (0, 1),
-        }
-    }
+fn parse_input(input: &str) -> Vec<Instruction> {
+    try_lines(input).unwrap_or_else(|e| panic!("{e}"))
 }
 
-fn get_area(border: &[(isize, isize)], border_length: isize) -> isize {
-    // get area with shoelace formula (trapezoid variant)
-    // https://en.wikipedia.org/wiki/Shoelace_formula
-    let mut shoelace: isize = 0;
-    for n in 0..border.len() {
-        let (y1, x1) = border[n];
-        let (y2, x2) = border[(n + 1) % border.len()];
-        shoelace += (y1 + y2) * (x1 - x2);
-    }
-    let area = shoelace / 2;
+// the real instruction hidden in the color, five hex digits of length and one of direction
+fn parse_color(s: &str) -> Option<(Direction, i128)> {
+    let (hex, direction) = s.split_at_checked(s.len().checked_sub(1)?)?;
+    Some((
+        parse_hex_direction(direction)?,
+        i128::from_str_radix(hex, 16).ok()?,
+    ))
+}
 
-    // get interior by inverting pick's theorem formula
-    // https://en.wikipedia.org/wiki/Pick%27s_theorem
-    let interior = area + 1 - border_length / 2;
-    interior + border_length
+// the last hex digit encodes the direction clockwise starting with right
+fn parse_hex_direction(s: &str) -> Option<Direction> {
+    use Direction::*;
+    Some(match s {
+        "0" => Right,
+        "1" => Down,
+        "2" => Left,
+        "3" => Up,
+        _ => return None,
+    })
 }
 
-fn get_border(instructions: &[(Direction, isize)]) -> (Vec<(isize, isize)>, isize) {
+fn get_border(instructions: &[(Direction, i128)]) -> Vec<Point2<i128>> {
     let mut border = Vec::new();
-    let mut border_length = 0;
-    let (mut sy, mut sx) = (0, 0);
-    border.push((sy, sx));
+    let mut position = Point2::default();
 
     for (d, l) in instructions.iter().copied() {
-        let (dy, dx) = d.into();
-        (sy, sx) = (sy + l * dy, sx + l * dx);
-        border.push((sy, sx));
-        border_length += l;
+        position += d * l;
+        border.push(position);
     }
 
-    border.pop();
-
-    (border, border_length)
+    border
 }
 
-pub fn part_one(input: &str) -> Option<isize> {
-    let instructions: Vec<(Direction, isize)> = input
-        .lines()
-        .map(|line| {
-            let [d, l, _] = line.splitn(3, ' ').collect::<Vec<_>>().try_into().unwrap();
-            (d.parse().unwrap(), l.parse::<isize>().unwrap())
-        })
+pub fn part_one(input: &str) -> Option<i128> {
+    let instructions: Vec<(Direction, i128)> = parse_input(input)
+        .into_iter()
+        .map(|x| (x.direction, x.length))
         .collect();
 
-    let (border, border_length) = get_border(&instructions);
-    Some(get_area(&border, border_length))
+    // trenches are one meter wide, so the lagoon covers every lattice point of the border polygon
+    Some(polygon::lattice_points(&get_border(&instructions)))
 }
 
-fn join_option_tuple<T, U>((a, b): (Option<T>, Option<U>)) -> Option<(T, U)> {
-    Some((a?, b?))
-}
+pub fn part_two(input: &str) -> Option<i128> {
+    let instructions: Vec<(Direction, i128)> =
+        parse_input(input).into_iter().map(|x| x.color).collect();
 
-pub fn part_two(input: &str) -> Option<isize> {
-    let instructions: Vec<(Direction, isize)> = input
-        .lines()
-        .filter_map(|line| line.split_once(" (#"))
-        .filter_map(|(_, h)| h.strip_suffix(')'))
-        .map(|h| h.split_at(h.len() - 1))
-        .map(|(hex, dir)| (dir.parse().ok(), isize::from_str_radix(hex, 16).ok()))
-        .filter_map(join_option_tuple)
-        .collect();
-
-    let (border, border_length) = get_border(&instructions);
-    Some(get_area(&border, border_length))
+    // trenches are one meter wide, so the lagoon covers every lattice point of the border polygon
+    Some(polygon::lattice_points(&get_border(&instructions)))
 }
 
 aoc::solution!(18);
@@ -123,4 +86,11 @@ mod tests {
             Some(952408144115)
         );
     }
+    #[test]
+    fn test_parse_color() {
+        let instruction: Instruction = "R 6 (#70c710)".parse().unwrap();
+        assert_eq!(instruction.color, (Direction::Right, 461937));
+        assert!("R 6 (#)".parse::<Instruction>().is_err());
+        assert!("R 6 (#70c714)".parse::<Instruction>().is_err());
+    }
 }
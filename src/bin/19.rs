@@ -1,9 +1,11 @@
 use std::{
     collections::HashMap,
+    fmt::{self, Display},
     ops::{Index, IndexMut},
-    str::FromStr,
 };
 
+use aoc::{parsers::try_lines, ranges::Boxes};
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 enum Gear {
     X,
@@ -73,15 +75,30 @@ struct Workflow {
     workflows: Vec<WorkflowInner>,
 }
 
+/// The workflow or rule that didn't parse.
 #[derive(Debug)]
-struct ParseWorkflowError;
+struct ParseWorkflowError(String);
+
+impl Display for ParseWorkflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse {:?}", self.0)
+    }
+}
 
 impl TryFrom<&str> for Workflow {
     type Error = ParseWorkflowError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        Ok(Self {
-            workflows: value.split(',').filter_map(|x| x.try_into().ok()).collect(),
-        })
+        let workflows = value
+            .split(',')
+            .map(WorkflowInner::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // `apply` needs a last rule that matches every part
+        if !matches!(workflows.last(), Some(WorkflowInner::Resolver(_))) {
+            return Err(ParseWorkflowError(value.to_string()));
+        }
+
+        Ok(Self { workflows })
     }
 }
 
@@ -91,59 +108,41 @@ enum WorkflowInner {
     Rule((Gear, Comparator, usize, Resolver)),
 }
 
-#[derive(Debug)]
-struct ParseWorkflowInnerError;
-
 impl TryFrom<&str> for WorkflowInner {
-    type Error = ParseWorkflowInnerError;
+    type Error = ParseWorkflowError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if !value.contains(':') {
+        let error = || ParseWorkflowError(value.to_string());
+
+        let Some((rest, resolver)) = value.split_once(':') else {
+            if value.is_empty() {
+                return Err(error());
+            }
             return Ok(WorkflowInner::Resolver(value.into()));
+        };
+        if resolver.is_empty() {
+            return Err(error());
         }
-
-        let (rest, resolver) = value.split_once(':').unwrap();
         let resolver = resolver.into();
 
-        let (gear, number) = rest.split_once(|x| x == '<' || x == '>').unwrap();
-        let gear = gear.try_into().map_err(|_| ParseWorkflowInnerError)?;
-        let number = number.parse().map_err(|_| ParseWorkflowInnerError)?;
+        let (gear, number) = rest.split_once(['<', '>']).ok_or_else(error)?;
+        let gear = gear.try_into().map_err(|_| error())?;
+        let number = number.parse().map_err(|_| error())?;
 
-        let comparator = if value.contains('<') { '<' } else { '>' };
-        let comparator = comparator.try_into().map_err(|_| ParseWorkflowInnerError)?;
+        let comparator = if rest.contains('<') { '<' } else { '>' };
+        let comparator = comparator.try_into().map_err(|_| error())?;
 
         Ok(WorkflowInner::Rule((gear, comparator, number, resolver)))
     }
 }
 
-#[derive(Debug)]
-struct Xmas {
-    x: usize,
-    m: usize,
-    a: usize,
-    s: usize,
-}
-
-#[derive(Debug)]
-struct ParseXmasError;
-
-impl FromStr for Xmas {
-    type Err = ParseXmasError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.strip_prefix('{').ok_or(ParseXmasError)?;
-        let s = s.strip_suffix('}').ok_or(ParseXmasError)?;
-        let xmas: Vec<_> = s
-            .split(',')
-            .filter_map(|x| x.split_once('='))
-            .map(|x| x.1)
-            .filter_map(|x| x.parse().ok())
-            .collect();
-
-        Ok(Self {
-            x: xmas[0],
-            m: xmas[1],
-            a: xmas[2],
-            s: xmas[3],
-        })
+aoc::record! {
+    #[format("{{x={x},m={m},a={a},s={s}}}")]
+    #[derive(Debug)]
+    struct Xmas {
+        x: usize,
+        m: usize,
+        a: usize,
+        s: usize,
     }
 }
 
@@ -199,124 +198,61 @@ impl Xmas {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct XmasRange {
-    x: (usize, usize),
-    m: (usize, usize),
-    a: (usize, usize),
-    s: (usize, usize),
-}
+type XmasRange = Boxes<4>;
 
-impl Index<Gear> for XmasRange {
-    type Output = (usize, usize);
-    fn index(&self, index: Gear) -> &Self::Output {
-        use Gear::*;
-        match index {
-            X => &self.x,
-            M => &self.m,
-            A => &self.a,
-            S => &self.s,
-        }
-    }
-}
+fn divide(xmas_range: XmasRange, workflow: &Workflow) -> Vec<(XmasRange, Resolver)> {
+    let mut processed = Vec::new();
+    let mut rest = Some(xmas_range);
 
-impl IndexMut<Gear> for XmasRange {
-    fn index_mut(&mut self, index: Gear) -> &mut Self::Output {
-        use Gear::*;
-        match index {
-            X => &mut self.x,
-            M => &mut self.m,
-            A => &mut self.a,
-            S => &mut self.s,
-        }
-    }
-}
+    for w in workflow.workflows.iter() {
+        let Some(xmas_range) = rest.take() else {
+            break;
+        };
 
-impl XmasRange {
-    fn size(&self) -> usize {
-        (self.x.1 - self.x.0 + 1)
-            * (self.m.1 - self.m.0 + 1)
-            * (self.a.1 - self.a.0 + 1)
-            * (self.s.1 - self.s.0 + 1)
-    }
-
-    fn divide(self, workflow: &Workflow) -> Vec<(Self, Resolver)> {
-        let mut processed = Vec::new();
-        let mut rest = vec![self];
+        match w {
+            WorkflowInner::Resolver(r) => processed.push((xmas_range, r.clone())),
+            WorkflowInner::Rule((g, c, n, r)) => {
+                let (ok, other) = match c {
+                    Comparator::Gt => xmas_range.split_by(*g as usize, |x| x > *n),
+                    Comparator::Lt => xmas_range.split_by(*g as usize, |x| x < *n),
+                };
 
-        for w in workflow.workflows.iter() {
-            let mut new_rest = Vec::new();
-
-            while let Some(xmas_range) = rest.pop() {
-                match w {
-                    WorkflowInner::Resolver(r) => processed.push((xmas_range, r.clone())),
-                    WorkflowInner::Rule((g, c, n, r)) => {
-                        let compare = |x: usize, y: usize| match c {
-                            Comparator::Gt => x > y,
-                            Comparator::Lt => x < y,
-                        };
-
-                        let mut min_ok = usize::MAX;
-                        let mut max_ok = usize::MIN;
-                        let mut min_e = usize::MAX;
-                        let mut max_e = usize::MIN;
-
-                        for i in xmas_range[*g].0..=xmas_range[*g].1 {
-                            if compare(i, *n) {
-                                max_ok = if max_ok < i { i } else { max_ok };
-                                min_ok = if min_ok > i { i } else { min_ok };
-                            } else {
-                                max_e = if max_e < i { i } else { max_e };
-                                min_e = if min_e > i { i } else { min_e };
-                            }
-                        }
-
-                        if min_e <= max_e {
-                            let mut r = xmas_range;
-                            r[*g] = (min_e, max_e);
-                            new_rest.push(r);
-                        }
-                        if min_ok <= max_ok {
-                            let mut p = xmas_range;
-                            p[*g] = (min_ok, max_ok);
-                            processed.push((p, r.clone()));
-                        }
-                    }
+                rest = other;
+                if let Some(p) = ok {
+                    processed.push((p, r.clone()));
                 }
             }
-
-            rest = new_rest;
         }
-
-        processed
     }
+
+    processed
+}
+
+fn parse_workflow(line: &str) -> Result<(&str, Workflow), ParseWorkflowError> {
+    let (name, workflow) = line
+        .strip_suffix('}')
+        .and_then(|x| x.split_once('{'))
+        .ok_or_else(|| ParseWorkflowError(line.to_string()))?;
+    Ok((name, workflow.try_into()?))
 }
 
 fn build_map(input: &str) -> HashMap<&str, Workflow> {
-    let mut map = HashMap::new();
-    for (s, w) in input
+    input
         .split("\n\n")
         .next()
         .unwrap()
         .lines()
-        .filter_map(|x| x.strip_suffix('}'))
-        .filter_map(|x| x.split_once('{'))
-        .filter_map(|(s, w)| w.try_into().ok().map(|x| (s, x)))
-    {
-        map.insert(s, w);
-    }
-
-    map
+        .enumerate()
+        .map(|(i, line)| {
+            parse_workflow(line).unwrap_or_else(|e| panic!("in the workflows, line {}: {e}", i + 1))
+        })
+        .collect()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
     let map = build_map(input);
-    let xmas_vec: Vec<Xmas> = input
-        .split("\n\n")
-        .nth(1)?
-        .lines()
-        .filter_map(|x| x.parse().ok())
-        .collect();
+    let xmas_vec: Vec<Xmas> = try_lines(input.split("\n\n").nth(1)?)
+        .unwrap_or_else(|e| panic!("in the part ratings, {e}"));
 
     let mut total = 0;
 
@@ -341,12 +277,7 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     let mut finished = Vec::new();
     let mut stack = vec![(
-        XmasRange {
-            x: (1, 4000),
-            m: (1, 4000),
-            a: (1, 4000),
-            s: (1, 4000),
-        },
+        XmasRange::from_inclusive([1..=4000, 1..=4000, 1..=4000, 1..=4000]).unwrap(),
         Resolver::Delegated("in".to_string()),
     )];
 
@@ -358,12 +289,12 @@ pub fn part_two(input: &str) -> Option<usize> {
             }
             Resolver::Delegated(x) => {
                 let workflow = &map[x.as_str()];
-                stack.append(&mut xmas_range.divide(workflow))
+                stack.append(&mut divide(xmas_range, workflow))
             }
         }
     }
 
-    Some(finished.iter().map(XmasRange::size).sum())
+    Some(finished.iter().map(XmasRange::volume).sum())
 }
 aoc::solution!(19);
 
@@ -384,4 +315,19 @@ mod tests {
             Some(167409079868000)
         );
     }
+    #[test]
+    fn test_bad_workflows() {
+        assert!(Workflow::try_from("a<2006:qkq,m>2090:A,rfg").is_ok());
+        // no fallback, bad number, unknown gear, unknown comparator, missing resolver and rule
+        for bad in [
+            "a<2006:qkq,m>2090:A",
+            "a<x:qkq,R",
+            "q<1:A,R",
+            "a=1:A,R",
+            "a<1:,R",
+            "a<1:A,,R",
+        ] {
+            assert!(Workflow::try_from(bad).is_err(), "{bad}");
+        }
+    }
 }
@@ -1,9 +1,11 @@
 use std::{
     collections::{HashMap, VecDeque},
+    fmt::{self, Display},
+    process,
     str::FromStr,
 };
 
-use aoc::lcm;
+use aoc::{graph::Graph, lcm, template::Dot};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Module {
@@ -30,32 +32,34 @@ impl FromStr for Module {
 
 #[derive(Debug)]
 struct Node {
+    name: String,
     index: usize,
     module: Module,
     inputs: Vec<usize>,
     outputs: Vec<usize>,
 }
 
+/// Splits `%a` into its module and name, `broadcaster` keeps its whole name.
+fn split_module(from: &str) -> (&str, &str) {
+    match from.split_at(1) {
+        ("b", _) => ("b", from),
+        split => split,
+    }
+}
+
 fn parse_input(input: &str) -> Vec<Node> {
     let mut nodes = Vec::new();
     let mut mapper: HashMap<String, usize> = HashMap::new();
 
-    let output = Node {
-        index: 0,
-        module: Module::Output,
-        inputs: Vec::new(),
-        outputs: Vec::new(),
-    };
-    nodes.push(output);
-
     for line in input.lines() {
         let (from, _) = line.split_once(" -> ").unwrap();
-        let (module, name) = from.split_at(1);
+        let (module, name) = split_module(from);
         let module: Module = module.parse().unwrap();
 
         *mapper.entry(name.to_string()).or_default() = nodes.len();
 
         let l = Node {
+            name: name.to_string(),
             module,
             index: nodes.len(),
             inputs: Vec::new(),
@@ -67,11 +71,21 @@ fn parse_input(input: &str) -> Vec<Node> {
 
     for line in input.lines() {
         let (from, to) = line.split_once(" -> ").unwrap();
-        let (_, name) = from.split_at(1);
+        let (_, name) = split_module(from);
 
         let index = mapper[name];
         for destination in to.split(", ") {
-            let to_index = *mapper.get(destination).unwrap_or(&0);
+            // destinations without a module of their own, like `rx`, only receive pulses
+            let to_index = *mapper.entry(destination.to_string()).or_insert_with(|| {
+                nodes.push(Node {
+                    name: destination.to_string(),
+                    index: nodes.len(),
+                    module: Module::Output,
+                    inputs: Vec::new(),
+                    outputs: Vec::new(),
+                });
+                nodes.len() - 1
+            });
             nodes[index].outputs.push(to_index);
             nodes[to_index].inputs.push(index);
         }
@@ -80,104 +94,423 @@ fn parse_input(input: &str) -> Vec<Node> {
     nodes
 }
 
-pub fn cycle(input: &str, button_presses: Option<usize>) -> usize {
-    let mut nodes = parse_input(input);
+/// Presses simulated before giving up on `rx` when the network is not made of counters.
+const SIMULATION_LIMIT: usize = 1 << 20;
 
-    let mut graph = vec![vec![false; nodes.len()]; nodes.len()];
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pulse {
+    /// `None` for the button
+    from: Option<usize>,
+    to: usize,
+    high: bool,
+}
 
-    let mut highs = 0;
-    let mut lows = 0;
+#[derive(Debug)]
+struct Network {
+    nodes: Vec<Node>,
+    /// last pulse sent over each edge, the memory of conjunctions
+    memory: Vec<Vec<bool>>,
+    broadcaster: usize,
+}
 
-    let broadcaster = nodes
-        .iter()
-        .find(|x| x.module == Module::Broadcaster)
-        .unwrap()
-        .index;
-
-    let mut hm = HashMap::new();
-    let critical_node = nodes[0].inputs.first().copied();
-    let critical_inputs = critical_node.map(|x| nodes[x].inputs.len());
-
-    for i in 1..=button_presses.unwrap_or(usize::MAX) {
-        if let Some(c) = critical_inputs {
-            if button_presses.is_none() && hm.values().len() == c {
-                return hm.values().copied().reduce(lcm).unwrap_or(0);
+impl Network {
+    fn new(input: &str) -> Self {
+        let nodes = parse_input(input);
+        let memory = vec![vec![false; nodes.len()]; nodes.len()];
+        let broadcaster = nodes
+            .iter()
+            .find(|x| x.module == Module::Broadcaster)
+            .unwrap()
+            .index;
+
+        Network {
+            nodes,
+            memory,
+            broadcaster,
+        }
+    }
+
+    fn press(&mut self, on_pulse: impl FnMut(Pulse)) {
+        let button = Pulse {
+            from: None,
+            to: self.broadcaster,
+            high: false,
+        };
+        self.send(button, on_pulse);
+    }
+
+    /// Delivers `pulse` and everything it sets off, calling `on_pulse` for each in order.
+    fn send(&mut self, pulse: Pulse, mut on_pulse: impl FnMut(Pulse)) {
+        let mut queue = VecDeque::from([pulse]);
+
+        while let Some(pulse) = queue.pop_front() {
+            on_pulse(pulse);
+            let index = pulse.to;
+
+            let signal = match self.nodes[index].module {
+                Module::Output => continue,
+                Module::Broadcaster => pulse.high,
+                Module::FlipFlop(_) if pulse.high => continue,
+                Module::FlipFlop(high) => {
+                    self.nodes[index].module = Module::FlipFlop(!high);
+                    !high
+                }
+                Module::Conjuction => !self.nodes[index]
+                    .inputs
+                    .iter()
+                    .all(|&from| self.memory[from][index]),
+            };
+
+            for &to in self.nodes[index].outputs.iter() {
+                self.memory[index][to] = signal;
+                queue.push_back(Pulse {
+                    from: Some(index),
+                    to,
+                    high: signal,
+                });
             }
         }
+    }
 
-        let mut stack = VecDeque::from([(broadcaster, false)]);
+    /// Flip-flop states and outgoing memories of `nodes`, enough to tell if they are back where
+    /// they started.
+    fn state(&self, nodes: &[usize]) -> Vec<bool> {
+        let mut state = Vec::new();
+        for &index in nodes {
+            if let Module::FlipFlop(high) = self.nodes[index].module {
+                state.push(high);
+            }
+            state.extend(
+                self.nodes[index]
+                    .outputs
+                    .iter()
+                    .map(|&to| self.memory[index][to]),
+            );
+        }
+        state
+    }
 
-        while let Some((index, signal)) = stack.pop_front() {
-            if signal {
-                highs += 1;
-            } else {
-                lows += 1;
+    /// Pulses of each of the next `presses` presses, only those from or to `module` if given.
+    fn trace(&mut self, presses: usize, module: Option<&str>) -> Vec<Vec<Pulse>> {
+        let involves = |network: &Network, pulse: Pulse| match module {
+            None => true,
+            Some(name) => {
+                pulse.from.is_some_and(|x| network.nodes[x].name == name)
+                    || network.nodes[pulse.to].name == name
             }
+        };
+
+        (0..presses)
+            .map(|_| {
+                let mut pulses = Vec::new();
+                self.press(|pulse| pulses.push(pulse));
+                pulses.retain(|&pulse| involves(self, pulse));
+                pulses
+            })
+            .collect()
+    }
 
-            let node = &nodes[index];
-            let mut new_module = None;
+    /// A pulse the way the puzzle writes them, `button -low-> broadcaster`.
+    fn describe(&self, pulse: Pulse) -> String {
+        let from = pulse.from.map_or("button", |x| &self.nodes[x].name);
+        let signal = if pulse.high { "high" } else { "low" };
+        format!("{from} -{signal}-> {}", self.nodes[pulse.to].name)
+    }
 
+    /// One line per flip-flop and conjunction with what it remembers.
+    fn describe_state(&self) -> String {
+        let mut lines = Vec::new();
+        for node in self.nodes.iter() {
             match node.module {
-                Module::Output => (),
-                Module::Broadcaster => {
-                    for dest_index in node.outputs.iter().copied() {
-                        stack.push_back((dest_index, false));
-                    }
-                }
                 Module::FlipFlop(high) => {
-                    if !signal {
-                        let signal = !high;
-
-                        for dest_index in nodes[index].outputs.iter().copied() {
-                            graph[index][dest_index] = signal;
-                            stack.push_back((dest_index, signal));
-                        }
-
-                        new_module = Some(Module::FlipFlop(signal));
-                    }
+                    let state = if high { "on" } else { "off" };
+                    lines.push(format!("%{}: {state}", node.name));
                 }
                 Module::Conjuction => {
-                    let mut all = true;
-
-                    for in_index in nodes[index].inputs.iter().copied() {
-                        all &= graph[in_index][index];
-                    }
-
-                    for dest_index in nodes[index].outputs.iter().copied() {
-                        graph[index][dest_index] = !all;
-                        stack.push_back((dest_index, !all));
-                    }
-
-                    if let Some(c) = critical_node {
-                        if index == c {
-                            for in_index in nodes[index].inputs.iter().copied() {
-                                if graph[in_index][index] {
-                                    hm.entry(in_index).or_insert(i);
-                                }
-                            }
-                        }
-                    }
+                    let memory: Vec<String> = node
+                        .inputs
+                        .iter()
+                        .map(|&from| {
+                            let signal = if self.memory[from][node.index] {
+                                "high"
+                            } else {
+                                "low"
+                            };
+                            format!("{}={signal}", self.nodes[from].name)
+                        })
+                        .collect();
+                    lines.push(format!("&{}: {}", node.name, memory.join(", ")));
                 }
+                Module::Broadcaster | Module::Output => (),
             }
+        }
+        lines.join("\n")
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.nodes.iter().position(|x| x.name == name)
+    }
+
+    /// Number of presses until `output` gets a low pulse, by brute force.
+    fn simulate(&mut self, output: usize, limit: usize) -> Option<usize> {
+        (1..=limit).find(|_| {
+            let mut low = false;
+            self.press(|pulse| low |= pulse.to == output && !pulse.high);
+            low
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Assumption {
+    OneInput(usize),
+    Conjuction(String),
+    Shared(String),
+    Exits(usize),
+    Unreached(String),
+    NeverHigh(String),
+    NotCycle(String, usize),
+}
+
+impl Display for Assumption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Assumption::OneInput(n) => write!(f, "the output has {n} inputs instead of one"),
+            Assumption::Conjuction(name) => {
+                write!(f, "{name} before the output is not a conjunction")
+            }
+            Assumption::Shared(name) => {
+                write!(f, "{name} is reached from more than one broadcaster output")
+            }
+            Assumption::Exits(n) => {
+                write!(
+                    f,
+                    "a counter feeds {n} inputs of the conjunction instead of one"
+                )
+            }
+            Assumption::Unreached(name) => {
+                write!(f, "{name} is not part of any counter")
+            }
+            Assumption::NeverHigh(name) => {
+                write!(
+                    f,
+                    "{name} sends no high pulse in {SIMULATION_LIMIT} presses"
+                )
+            }
+            Assumption::NotCycle(name, presses) => {
+                write!(
+                    f,
+                    "the counter of {name} is not back to its start after {presses} presses"
+                )
+            }
+        }
+    }
+}
 
-            let node = &mut nodes[index];
-            if let Some(module) = new_module {
-                node.module = module;
+/// Splits the network into counters, one per broadcaster output, each feeding one input of the
+/// conjunction in front of `output`. Every counter sends a high pulse and resets on the same
+/// press, so `output` gets its low pulse at the lcm of their periods.
+fn counters(network: &mut Network, output: usize) -> Result<usize, Assumption> {
+    let inputs = &network.nodes[output].inputs;
+    let &[hub] = inputs.as_slice() else {
+        return Err(Assumption::OneInput(inputs.len()));
+    };
+    if network.nodes[hub].module != Module::Conjuction {
+        return Err(Assumption::Conjuction(network.nodes[hub].name.clone()));
+    }
+
+    let mut owner = vec![None; network.nodes.len()];
+    let mut components = Vec::new();
+    for (c, &start) in network.nodes[network.broadcaster]
+        .outputs
+        .iter()
+        .enumerate()
+    {
+        let mut component = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(index) = queue.pop_front() {
+            match owner[index] {
+                Some(o) if o == c => continue,
+                Some(_) => return Err(Assumption::Shared(network.nodes[index].name.clone())),
+                None => owner[index] = Some(c),
             }
+            for &to in network.nodes[index].outputs.iter() {
+                if to != hub && to != output && owner[to] != Some(c) {
+                    component.push(to);
+                    queue.push_back(to);
+                }
+            }
+        }
+        component.sort_unstable();
+        component.dedup();
+        components.push((start, component));
+    }
+
+    if let Some(&index) = network.nodes[hub]
+        .inputs
+        .iter()
+        .find(|&&x| owner[x].is_none())
+    {
+        return Err(Assumption::Unreached(network.nodes[index].name.clone()));
+    }
+
+    let mut periods = Vec::new();
+    for (start, component) in components {
+        let exits: Vec<usize> = network.nodes[hub]
+            .inputs
+            .iter()
+            .copied()
+            .filter(|x| component.contains(x))
+            .collect();
+        let exit = match exits.as_slice() {
+            [] => continue,
+            &[exit] => exit,
+            _ => return Err(Assumption::Exits(exits.len())),
+        };
+
+        let first = Pulse {
+            from: Some(network.broadcaster),
+            to: start,
+            high: false,
+        };
+        let presses_until_high = |network: &mut Network, limit: usize| {
+            (1..=limit).find(|_| {
+                let mut high = false;
+                network.send(first, |pulse| {
+                    high |= pulse.from == Some(exit) && pulse.to == hub && pulse.high
+                });
+                high
+            })
+        };
+
+        let name = network.nodes[exit].name.clone();
+        let Some(period) = presses_until_high(network, SIMULATION_LIMIT) else {
+            return Err(Assumption::NeverHigh(name));
+        };
+        // conjunctions only settle once they fired, so compare against the next period
+        let state = network.state(&component);
+        if presses_until_high(network, period) != Some(period) || network.state(&component) != state
+        {
+            return Err(Assumption::NotCycle(name, period));
         }
+        periods.push(period);
     }
 
-    highs * lows
+    Ok(periods.into_iter().fold(1, lcm))
 }
+
 pub fn part_one(input: &str) -> Option<usize> {
-    Some(cycle(input, Some(1000)))
+    let mut network = Network::new(input);
+    let (mut highs, mut lows) = (0, 0);
+    for _ in 0..1000 {
+        network.press(|pulse| {
+            if pulse.high {
+                highs += 1;
+            } else {
+                lows += 1;
+            }
+        });
+    }
+    Some(highs * lows)
+}
+
+/// Presses until `rx` gets a low pulse, along with the assumption that kept `counters` from
+/// finding them if the simulation had to.
+fn presses_until_rx(input: &str) -> (Option<usize>, Option<Assumption>) {
+    let mut network = Network::new(input);
+    let Some(rx) = network.find("rx") else {
+        return (None, None);
+    };
+
+    match counters(&mut network, rx) {
+        Ok(presses) => (Some(presses), None),
+        Err(assumption) => {
+            let presses = Network::new(input).simulate(rx, SIMULATION_LIMIT);
+            (presses, Some(assumption))
+        }
+    }
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    Some(cycle(input, None))
+    presses_until_rx(input).0
+}
+
+fn to_dot(input: &str) -> Dot {
+    let nodes = parse_input(input);
+
+    let mut graph: Graph<&str> = Graph::new();
+    for node in nodes.iter() {
+        graph.node(&node.name);
+    }
+    for node in nodes.iter() {
+        for &to in node.outputs.iter() {
+            graph.add_edge(node.index, to, 1);
+        }
+    }
+
+    Dot(graph.to_dot(false, |i, name| {
+        let shape = match nodes[i].module {
+            Module::Broadcaster => "house",
+            Module::FlipFlop(_) => "box",
+            Module::Conjuction => "invtrapezium",
+            Module::Output => "doublecircle",
+        };
+        format!("label={name:?} shape={shape}")
+    }))
+}
+
+fn usage() -> ! {
+    eprintln!("usage: cargo solve 20 -- --trace [--presses <n>] [--module <name>] [--state]");
+    process::exit(1)
 }
 
-aoc::solution!(20);
+fn trace(input: &str) {
+    let presses = match aoc::template::flag_value("presses") {
+        None if !aoc::template::flag_requested("presses") => 1,
+        value => value
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_else(|| usage()),
+    };
+    let module = aoc::template::flag_value("module");
+    if module.is_none() && aoc::template::flag_requested("module") {
+        usage();
+    }
+
+    let mut network = Network::new(input);
+    if let Some(name) = module.as_deref().filter(|&x| network.find(x).is_none()) {
+        eprintln!("there is no module {name:?}");
+        usage();
+    }
+    for (i, pulses) in network.trace(presses, module.as_deref()).iter().enumerate() {
+        println!("press {}", i + 1);
+        for &pulse in pulses {
+            println!("  {}", network.describe(pulse));
+        }
+    }
+    if aoc::template::flag_requested("state") {
+        println!("state after press {presses}");
+        println!("{}", network.describe_state());
+    }
+}
+
+/// How part two got its answer, from the counters or by simulating because of which assumption.
+fn explain(input: &str) {
+    match presses_until_rx(input) {
+        (None, None) => println!("no module sends pulses to rx"),
+        (Some(presses), None) => {
+            println!("rx gets a low pulse after {presses} presses, the lcm of the counter periods")
+        }
+        (presses, Some(assumption)) => {
+            println!("{assumption}, simulating instead");
+            match presses {
+                Some(presses) => println!("rx gets a low pulse after {presses} presses"),
+                None => println!("rx gets no low pulse in {SIMULATION_LIMIT} presses"),
+            }
+        }
+    }
+}
+
+aoc::solution!(20, dot = to_dot, trace = trace, explain = explain);
 
 #[cfg(test)]
 mod tests {
@@ -197,10 +530,122 @@ mod tests {
         );
     }
     #[test]
+    fn test_counters() {
+        // counters of 3 and 5 presses
+        let input = "broadcaster -> f1, g1
+%f1 -> f2, k
+%f2 -> k
+&k -> f1, e
+&e -> hub
+%g1 -> g2, l
+%g2 -> g3
+%g3 -> l
+&l -> g1, g2, m
+&m -> hub
+&hub -> rx";
+        let mut network = Network::new(input);
+        let rx = network.find("rx").unwrap();
+        assert_eq!(counters(&mut network, rx), Ok(15));
+        assert_eq!(Network::new(input).simulate(rx, 100), Some(15));
+        assert_eq!(presses_until_rx(input), (Some(15), None));
+        assert_eq!(part_two(input), Some(15));
+
+        // both inputs of `con` belong to the one counter, and nothing sends to `rx`
+        let input = aoc::template::read_file_part("examples", 20, 2);
+        let mut network = Network::new(&input);
+        let output = network.find("output").unwrap();
+        assert_eq!(counters(&mut network, output), Err(Assumption::Exits(2)));
+        assert_eq!(presses_until_rx(&input), (None, None));
+    }
+    #[test]
+    fn test_trace() {
+        let mut network = Network::new(&aoc::template::read_file_part("examples", 20, 1));
+        let pulses = network.trace(1, None);
+        let described: Vec<String> = pulses[0].iter().map(|&x| network.describe(x)).collect();
+        assert_eq!(
+            described,
+            [
+                "button -low-> broadcaster",
+                "broadcaster -low-> a",
+                "broadcaster -low-> b",
+                "broadcaster -low-> c",
+                "a -high-> b",
+                "b -high-> c",
+                "c -high-> inv",
+                "inv -low-> a",
+                "a -low-> b",
+                "b -low-> c",
+                "c -low-> inv",
+                "inv -high-> a",
+            ]
+        );
+
+        let mut network = Network::new(&aoc::template::read_file_part("examples", 20, 2));
+        let pulses = network.trace(2, Some("output"));
+        let described: Vec<Vec<String>> = pulses
+            .iter()
+            .map(|press| press.iter().map(|&x| network.describe(x)).collect())
+            .collect();
+        assert_eq!(
+            described,
+            [
+                vec!["con -high-> output", "con -low-> output"],
+                vec!["con -high-> output"],
+            ]
+        );
+        assert_eq!(
+            network.describe_state(),
+            "%a: off\n&inv: a=low\n%b: on\n&con: a=low, b=high"
+        );
+    }
+    #[test]
+    fn test_sinks() {
+        let mut network = Network::new("broadcaster -> a, b\n%a -> out\n%b -> rx, out");
+        let pulses = network.trace(1, None);
+        let described: Vec<String> = pulses[0].iter().map(|&x| network.describe(x)).collect();
+        assert_eq!(
+            described,
+            [
+                "button -low-> broadcaster",
+                "broadcaster -low-> a",
+                "broadcaster -low-> b",
+                "a -high-> out",
+                "b -high-> rx",
+                "b -high-> out",
+            ]
+        );
+        assert_eq!(network.nodes[network.find("out").unwrap()].inputs.len(), 2);
+    }
+    #[test]
     fn test_part_two() {
+        // the examples have no `rx`, `test_counters` and `test_assumptions` build their own
         assert_eq!(
             part_two(&aoc::template::read_file_part("examples", 20, 2)),
-            Some(1)
+            None
+        );
+    }
+    #[test]
+    fn test_assumptions() {
+        // `x` belongs to the counters of both `a` and `b`
+        let input = "broadcaster -> a, b
+%a -> x
+%b -> x
+&x -> hub
+&hub -> rx";
+        assert_eq!(
+            presses_until_rx(input),
+            (Some(2), Some(Assumption::Shared("x".to_string())))
+        );
+
+        // `e` fires on every press but `b` only catches up on the second one
+        let input = "broadcaster -> a
+%a -> e, b
+%b -> e
+&e -> hub
+&hub -> rx";
+        assert_eq!(
+            presses_until_rx(input),
+            (Some(1), Some(Assumption::NotCycle("e".to_string(), 1)))
         );
     }
 }
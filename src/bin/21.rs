@@ -1,60 +1,57 @@
-use std::{collections::HashSet, usize};
+use aoc::{collections::BitGrid, grid::Grid, math::poly::extrapolate};
 
-fn parse_input(input: &str) -> (Vec<Vec<u8>>, (isize, isize)) {
-    let mut grid: Vec<_> = input.lines().map(|x| x.as_bytes().to_vec()).collect();
+fn parse_input(input: &str) -> (Grid<u8>, (isize, isize)) {
+    let mut grid: Grid<u8> = input.parse().unwrap();
+    let start = grid.find(&b'S').unwrap();
 
-    let start = grid
-        .iter()
-        .enumerate()
-        .flat_map(|(y, item)| item.iter().enumerate().map(move |(x, item)| (x, y, *item)))
-        .find_map(|(x, y, s)| if s == b'S' { Some((y, x)) } else { None })
-        .unwrap();
-
-    grid[start.0][start.1] = b'.';
+    grid[start] = b'.';
 
     (grid, (start.0 as isize, start.1 as isize))
 }
 
 fn walk_return_at(
-    grid: &[Vec<u8>],
+    grid: &Grid<u8>,
     start: (isize, isize),
     mut returns: Vec<usize>,
     can_cycle: bool,
 ) -> Vec<usize> {
     returns.sort_by(|a, b| b.cmp(a));
 
-    let h = grid.len() as isize;
-    let w = grid[0].len() as isize;
-
-    let invalid_indexing = |y, x| y < 0 || y >= h || x < 0 || x >= w;
+    let h = grid.height() as isize;
+    let w = grid.width() as isize;
 
     let mut results = Vec::new();
     let length = returns[0];
     let mut next = returns.pop().unwrap();
 
-    let mut visited = HashSet::new();
-    visited.insert(start);
+    // nothing gets more than `length` steps away from the start, so a padded grid fits it all
+    let pad = length as isize;
+    let padded = |(y, x): (isize, isize)| ((y + pad) as usize, (x + pad) as usize);
 
-    for i in 1..=length {
-        let mut new_visited = HashSet::new();
+    let mut visited = BitGrid::new(grid.height() + 2 * length, grid.width() + 2 * length);
+    let mut new_visited = visited.clone();
+    visited.insert(padded(start));
 
+    for i in 1..=length {
         for (y, x) in visited.iter() {
+            let (y, x) = (y as isize - pad, x as isize - pad);
             for (dy, dx) in [(1, 0), (0, 1), (-1, 0), (0, -1)] {
                 let (ny, nx) = (y + dy, x + dx);
 
-                if !can_cycle && invalid_indexing(ny, nx) {
+                if !can_cycle && grid.position(ny, nx).is_none() {
                     continue;
                 }
 
                 let (cy, cx) = (ny.rem_euclid(h) as usize, nx.rem_euclid(w) as usize);
 
-                if grid[cy][cx] == b'.' {
-                    new_visited.insert((ny, nx));
+                if grid[(cy, cx)] == b'.' {
+                    new_visited.insert(padded((ny, nx)));
                 }
             }
         }
 
-        visited = new_visited;
+        std::mem::swap(&mut visited, &mut new_visited);
+        new_visited.clear();
 
         if i == next {
             results.push(visited.len());
@@ -77,18 +74,16 @@ pub fn part_one(input: &str) -> Option<usize> {
 pub fn part_two(input: &str) -> Option<usize> {
     let (grid, start) = parse_input(input);
 
-    let h = grid.len();
+    let h = grid.height();
     let s = start.0 as usize;
 
+    // the reachable count grows quadratically every time another grid width is crossed
     let result = walk_return_at(&grid, start, vec![s, s + h, s + 2 * h], true);
-
-    let a = (result[2] - 2 * result[1] + result[0]) / 2;
-    let b = (result[1] - result[0]) - a;
-    let c = result[0];
+    let samples: Vec<_> = result.into_iter().map(|x| x as i128).collect();
 
     let x = 26501365 / h;
 
-    Some(a * x * x + b * x + c)
+    extrapolate(&samples, x as i128)?.try_into().ok()
 }
 
 aoc::solution!(21);
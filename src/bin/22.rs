@@ -1,9 +1,10 @@
-use std::{collections::HashMap, str::FromStr};
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+use aoc::{collections::FastHashMap, ranges::Boxes};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
 struct Brick {
-    from: (usize, usize, usize),
-    to: (usize, usize, usize),
+    cube: Boxes<3>,
 }
 
 #[derive(Debug)]
@@ -18,40 +19,23 @@ impl FromStr for Brick {
             .split(',')
             .filter_map(|y| y.parse().ok())
             .zip(second.split(',').filter_map(|y| y.parse().ok()))
-            .map(|(x, y)| if x < y { (x, y) } else { (y, x) })
+            .map(|(x, y)| if x < y { x..=y } else { y..=x })
             .collect();
-        let from = (sorted[0].0, sorted[1].0, sorted[2].0);
-        let to = (sorted[0].1, sorted[1].1, sorted[2].1);
-
-        Ok(Self { from, to })
-    }
-}
+        let axes = sorted.try_into().map_err(|_| ParseBrickError)?;
 
-impl Brick {
-    fn height(&self) -> usize {
-        self.to.2 - self.from.2 + 1
-    }
-
-    fn points(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
-        (self.from.0..=self.to.0).flat_map(move |x| {
-            (self.from.1..=self.to.1)
-                .flat_map(move |y| (self.from.2..=self.to.2).map(move |z| (x, y, z)))
+        Ok(Self {
+            cube: Boxes::from_inclusive(axes).map_err(|_| ParseBrickError)?,
         })
     }
 }
 
-impl PartialOrd for Brick {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+impl Brick {
+    fn bottom(&self) -> usize {
+        self.cube.axes[2].start
     }
-}
 
-impl Ord for Brick {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.from
-            .2
-            .cmp(&other.from.2)
-            .then((self.from.0, self.from.1).cmp(&(other.from.0, other.from.1)))
+    fn height(&self) -> usize {
+        self.cube.size(2)
     }
 }
 
@@ -62,14 +46,14 @@ struct Tower {
 
 impl Tower {
     fn new(mut bricks: Vec<Brick>) -> Self {
-        bricks.sort();
+        bricks.sort_by_key(|x| (x.bottom(), x.cube.axes[0].start, x.cube.axes[1].start));
         let mut ret = Self { bricks };
         ret.compress(None);
         ret
     }
 
     fn compress(&mut self, skip: Option<usize>) -> usize {
-        let mut heights: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut heights: FastHashMap<(usize, usize), usize> = FastHashMap::default();
         let mut moved = 0;
 
         for brick in self
@@ -81,22 +65,22 @@ impl Tower {
         {
             let height = brick.height();
             let new_height = brick
+                .cube
                 .points()
-                .map(|(x, y, _)| *heights.entry((x, y)).or_default())
+                .map(|[x, y, _]| *heights.entry((x, y)).or_default())
                 .max()
                 .unwrap();
 
-            for (x, y, _) in brick.points() {
+            for [x, y, _] in brick.cube.points() {
                 *heights.get_mut(&(x, y)).unwrap() = new_height + height;
             }
 
-            if new_height + 1 == brick.from.2 {
+            if new_height + 1 == brick.bottom() {
                 continue;
             }
 
             if skip.is_none() {
-                brick.from.2 = new_height + 1;
-                brick.to.2 = new_height + height;
+                brick.cube.axes[2] = new_height + 1..new_height + 1 + height;
             }
 
             moved += 1;
@@ -1,65 +1,31 @@
-use std::{
-    collections::{HashMap, HashSet},
-    iter::once,
-};
+use aoc::{collections::BitGrid, geom::Direction, graph::Graph, grid::Grid, template::Dot};
 
-const DIRS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+fn build_graph(input: &str, two_way: bool) -> (Graph<(usize, usize)>, usize, usize) {
+    let grid: Grid<u8> = input.parse().unwrap();
 
-#[derive(Debug, Default)]
-struct Node {
-    neighbours: Vec<(usize, usize)>,
-}
-
-fn build_graph(input: &str, two_way: bool) -> (Vec<Node>, usize) {
-    let mut grid: Vec<Vec<_>> = input.lines().map(|x| x.as_bytes().to_vec()).collect();
-
-    let l = grid[0].len();
-
-    let border: Vec<_> = once(b'#').cycle().take(l).collect();
-    grid.insert(0, border.clone());
-    grid.push(border);
-
-    let start = (1, 1);
-    let end = (grid.len() - 2, grid[0].len() - 2);
-
-    let mut nodes = Vec::new();
+    let start = (0, 1);
+    let end = (grid.height() - 1, grid.width() - 2);
 
-    let mut mapper = HashMap::new();
-    mapper.insert(start, 0);
+    let mut graph = Graph::new();
+    graph.node(start);
 
-    let mut visited: HashSet<(usize, usize)> = HashSet::new();
+    let mut visited = BitGrid::new(grid.height(), grid.width());
 
     let mut stack = Vec::new();
     // (from_node, at, len)
     stack.push((start, start, 0));
 
-    let forced = |x| match x {
-        b'>' => (0, 1),
-        b'v' => (1, 0),
-        _ => (0, 0),
-    };
-
     while let Some((from, (y, x), len)) = stack.pop() {
-        let is_node = DIRS
-            .iter()
-            .filter(|(dy, dx)| {
-                grid[((y as isize) + dy) as usize][((x as isize) + dx) as usize] != b'#'
-            })
-            .count()
-            > 2
+        let is_node = grid.neighbours((y, x)).filter(|&n| grid[n] != b'#').count() > 2
             || (y, x) == start
             || (y, x) == end;
 
         if is_node {
-            if !visited.contains(&(y, x)) {
-                mapper.insert((y, x), nodes.len());
-                nodes.push(Node::default());
-            }
-
-            if from != (y, x) {
-                nodes[mapper[&from]].neighbours.push((mapper[&(y, x)], len));
+            let (a, b) = (graph.node(from), graph.node((y, x)));
+            if a != b {
+                graph.add_edge(a, b, len);
                 if two_way {
-                    nodes[mapper[&(y, x)]].neighbours.push((mapper[&from], len));
+                    graph.add_edge(b, a, len);
                 }
             }
         }
@@ -67,21 +33,12 @@ fn build_graph(input: &str, two_way: bool) -> (Vec<Node>, usize) {
         if !visited.insert((y, x)) {
             continue;
         }
-        for (ny, nx, _, _) in DIRS
-            .iter()
-            .map(|(dy, dx)| {
-                (
-                    ((y as isize) + dy) as usize,
-                    ((x as isize) + dx) as usize,
-                    dy,
-                    dx,
-                )
-            })
-            .filter(|(ny, nx, _, _)| grid[*ny][*nx] != b'#')
-            .filter(|(ny, nx, dy, dx)| {
-                let (fy, fx) = forced(grid[*ny][*nx]);
-                (fy + **dy, fx + **dx) != (0, 0)
-            })
+        // slopes can't be climbed from the opposite side
+        for (_, (ny, nx)) in Direction::ALL
+            .into_iter()
+            .filter_map(|d| grid.offset((y, x), d.into()).map(|n| (d, n)))
+            .filter(|&(_, n)| grid[n] != b'#')
+            .filter(|&(d, n)| Direction::try_from(grid[n] as char) != Ok(-d))
         {
             let new_len = if is_node { 1 } else { len + 1 };
             let new_from = if is_node { (y, x) } else { from };
@@ -89,57 +46,33 @@ fn build_graph(input: &str, two_way: bool) -> (Vec<Node>, usize) {
         }
     }
 
-    (nodes, mapper[&end])
-}
-
-fn longest_path(
-    nodes: &[Node],
-    mut visited: usize,
-    location: usize,
-    target: usize,
-    length: usize,
-) -> Option<usize> {
-    if location == target {
-        return Some(length);
-    }
-
-    // binary mask for visited since < 64 nodes in input
-    // nth bit tells if location n was visited already
-    visited |= 1 << location;
-
-    let mut max_len = 0;
-
-    for (n, l) in nodes[location]
-        .neighbours
-        .iter()
-        .copied()
-        .filter(|(n, _)| (visited & 1 << n) == 0)
-    {
-        if let Some(new_len) = longest_path(nodes, visited, n, target, length + l) {
-            if max_len < new_len {
-                max_len = new_len;
-            }
-        }
-    }
-
-    if max_len > 0 {
-        Some(max_len)
-    } else {
-        None
-    }
+    let (start, end) = (graph.node(start), graph.node(end));
+    (graph, start, end)
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let (nodes, target) = build_graph(input, false);
-    longest_path(&nodes, 0, 0, target, 0)
+    let (graph, start, end) = build_graph(input, false);
+    graph.longest_path(start, end)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let (nodes, target) = build_graph(input, true);
-    longest_path(&nodes, 0, 0, target, 0)
+    let (graph, start, end) = build_graph(input, true);
+    graph.longest_path(start, end)
+}
+
+fn to_dot(input: &str) -> Dot {
+    let (graph, start, end) = build_graph(input, true);
+    Dot(graph.to_dot(true, |i, (y, x)| {
+        let shape = if i == start || i == end {
+            "doublecircle"
+        } else {
+            "circle"
+        };
+        format!("label=\"{y},{x}\" shape={shape}")
+    }))
 }
 
-aoc::solution!(23);
+aoc::solution!(23, dot = to_dot);
 
 #[cfg(test)]
 mod tests {
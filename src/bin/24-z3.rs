@@ -0,0 +1,67 @@
+// Day 24 part two handed to Z3, to cross-check the solver in 24.rs:
+// `cargo solve 24-z3 --features z3`
+
+#[cfg(feature = "z3")]
+use aoc::parsers::try_lines;
+
+#[cfg(feature = "z3")]
+aoc::record! {
+    #[format("{x}, {y}, {z} @ {dx}, {dy}, {dz}")]
+    struct Hailstone {
+        x: i64,
+        y: i64,
+        z: i64,
+        dx: i64,
+        dy: i64,
+        dz: i64,
+    }
+}
+
+// crossings in the xy plane need no solver, see 24.rs
+#[cfg(feature = "z3")]
+pub fn part_one(_input: &str) -> Option<usize> {
+    None
+}
+
+#[cfg(feature = "z3")]
+pub fn part_two(input: &str) -> Option<i64> {
+    use z3::ast::{Ast, Int};
+
+    let hailstones: Vec<Hailstone> = try_lines(input).unwrap_or_else(|e| panic!("{e}"));
+
+    let ctx = z3::Context::new(&z3::Config::new());
+    let solver = z3::Solver::new(&ctx);
+
+    let zero = Int::from_u64(&ctx, 0);
+    let [a, b, c, da, db, dc] = ["a", "b", "c", "da", "db", "dc"].map(|x| Int::new_const(&ctx, x));
+
+    for (i, h) in hailstones.iter().enumerate().take(3) {
+        let [x, y, z, dx, dy, dz] =
+            [h.x, h.y, h.z, h.dx, h.dy, h.dz].map(|w| Int::from_i64(&ctx, w));
+        let t = Int::new_const(&ctx, format!("t_{i}").as_str());
+        solver.assert(&t.ge(&zero));
+        solver.assert(&((&x + &dx * &t)._eq(&(&a + &da * &t))));
+        solver.assert(&((&y + &dy * &t)._eq(&(&b + &db * &t))));
+        solver.assert(&((&z + &dz * &t)._eq(&(&c + &dc * &t))));
+    }
+
+    if solver.check() != z3::SatResult::Sat {
+        return None;
+    }
+    let model = solver.get_model()?;
+    model.eval(&(&a + &b + &c), true)?.as_i64()
+}
+
+aoc::solution!(24, feature = "z3");
+
+#[cfg(all(test, feature = "z3"))]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_part_two() {
+        assert_eq!(
+            part_two(&aoc::template::read_file("examples", 24)),
+            Some(47)
+        );
+    }
+}
@@ -1,75 +1,207 @@
-use z3::ast::{Ast, Int};
-
-type Vector3 = (f64, f64, f64);
-
-fn parse_input(input: &str) -> Vec<(Vector3, Vector3)> {
-    input
-        .lines()
-        .filter_map(|x| x.split_once(" @ "))
-        .map(|(a, b)| {
-            let av: Vec<_> = a.splitn(3, ", ").filter_map(|x| x.parse().ok()).collect();
-            let bv: Vec<_> = b.splitn(3, ", ").filter_map(|x| x.parse().ok()).collect();
-            ((av[0], av[1], av[2]), (bv[0], bv[1], bv[2]))
-        })
-        .collect()
+use aoc::{
+    geom::Point3,
+    math::{checked_gcd, linalg, rational::Rational},
+    parsers::try_lines,
+};
+
+aoc::record! {
+    #[format("{x}, {y}, {z} @ {dx}, {dy}, {dz}")]
+    #[derive(Debug, Clone, Copy)]
+    struct Hailstone {
+        x: i128,
+        y: i128,
+        z: i128,
+        dx: i128,
+        dy: i128,
+        dz: i128,
+    }
 }
 
-fn part_one_with_area(input: &str, min: f64, max: f64) -> u32 {
-    let points = parse_input(input);
+impl Hailstone {
+    fn position(&self) -> Point3<i128> {
+        Point3::new(self.x, self.y, self.z)
+    }
 
-    let mut c = 0;
+    fn velocity(&self) -> Point3<i128> {
+        Point3::new(self.dx, self.dy, self.dz)
+    }
+}
 
-    let test_area = |x: f64, y: f64| x <= max && x >= min && y <= max && y >= min;
+fn parse_input(input: &str) -> Vec<Hailstone> {
+    try_lines(input).unwrap_or_else(|e| panic!("{e}"))
+}
 
-    for (i, ((x1, y1, _), (dx1, dy1, _))) in points.iter().copied().enumerate() {
-        for ((x2, y2, _), (dx2, dy2, _)) in points.iter().skip(i + 1).copied() {
-            let n2 = (x2 - x1 + (y1 - y2) * dx1 / dy1) / (dy2 * dx1 / dy1 - dx2);
-            let n1 = (x1 - x2 + (y2 - y1) * dx2 / dy2) / (dy1 * dx2 / dy2 - dx1);
+// where the paths of two hailstones cross in the xy plane, if both get there in the future
+fn crossing(a: &Hailstone, b: &Hailstone) -> Option<(Rational, Rational)> {
+    let det = a.dx * b.dy - a.dy * b.dx;
+    if det == 0 {
+        return None;
+    }
 
-            if n1 <= 0.0 || n2 <= 0.0 {
-                continue;
-            }
+    let (ox, oy) = (b.x - a.x, b.y - a.y);
+    let ta = Rational::new(ox * b.dy - oy * b.dx, det);
+    let tb = Rational::new(ox * a.dy - oy * a.dx, det);
+    if ta <= Rational::from(0) || tb <= Rational::from(0) {
+        return None;
+    }
 
-            let x = x1 + n1 * dx1;
-            let y = y1 + n1 * dy1;
+    let x = Rational::from(a.x) + ta * Rational::from(a.dx);
+    let y = Rational::from(a.y) + ta * Rational::from(a.dy);
+    Some((x, y))
+}
+
+fn part_one_with_area(input: &str, min: i128, max: i128) -> usize {
+    let hailstones = parse_input(input);
+    let area = Rational::from(min)..=Rational::from(max);
 
-            if test_area(x, y) {
-                c += 1;
+    let mut c = 0;
+
+    for (i, a) in hailstones.iter().enumerate() {
+        for b in hailstones.iter().skip(i + 1) {
+            if let Some((x, y)) = crossing(a, b) {
+                if area.contains(&x) && area.contains(&y) {
+                    c += 1;
+                }
             }
         }
     }
 
     c
 }
-pub fn part_one(input: &str) -> Option<u32> {
-    let min = 200000000000000.0;
-    let max = 400000000000000.0;
-    Some(part_one_with_area(input, min, max))
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(part_one_with_area(
+        input,
+        200_000_000_000_000,
+        400_000_000_000_000,
+    ))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let points = parse_input(input);
+type Vector = Point3<Rational>;
 
-    let ctx = z3::Context::new(&z3::Config::new());
-    let solver = z3::Solver::new(&ctx);
+fn vector(v: Point3<i128>) -> Vector {
+    Point3::new(v.x.into(), v.y.into(), v.z.into())
+}
 
-    let zero = Int::from_u64(&ctx, 0);
-    let [a, b, c, da, db, dc] = ["a", "b", "c", "da", "db", "dc"].map(|x| Int::new_const(&ctx, x));
+// `a - b` and `a x b` with `Rational`'s checked operations, `None` on overflow
+fn difference(a: Vector, b: Vector) -> Option<Vector> {
+    Some(Point3::new(
+        a.x.checked_sub(b.x)?,
+        a.y.checked_sub(b.y)?,
+        a.z.checked_sub(b.z)?,
+    ))
+}
 
-    for (i, ((x, y, z), (dx, dy, dz))) in points.iter().enumerate().take(3) {
-        let [x, y, z, dx, dy, dz] = [x, y, z, dx, dy, dz].map(|w| Int::from_i64(&ctx, *w as i64));
-        let t = Int::new_const(&ctx, format!("t_{i}").as_str());
-        solver.assert(&t.ge(&zero));
-        solver.assert(&((&x + &dx * &t)._eq(&(&a + &da * &t))));
-        solver.assert(&((&y + &dy * &t)._eq(&(&b + &db * &t))));
-        solver.assert(&((&z + &dz * &t)._eq(&(&c + &dc * &t))));
+fn cross(a: Vector, b: Vector) -> Option<Vector> {
+    let det = |p: Rational, q: Rational, r: Rational, s: Rational| {
+        p.checked_mul(s)?.checked_sub(q.checked_mul(r)?)
+    };
+    Some(Point3::new(
+        det(a.y, a.z, b.y, b.z)?,
+        det(a.z, a.x, b.z, b.x)?,
+        det(a.x, a.y, b.x, b.y)?,
+    ))
+}
+
+// time at which a hailstone is on the line through the origin along `direction`
+fn time_on_line(position: Vector, velocity: Vector, direction: Vector) -> Option<Rational> {
+    // (position + t * velocity) x direction == 0, so t * velocity x direction == direction x position
+    let a = cross(velocity, direction)?;
+    let b = cross(direction, position)?;
+    let a = [[a.x], [a.y], [a.z]].map(Vec::from).to_vec();
+    Some(linalg::solve(&a, &[b.x, b.y, b.z])?[0])
+}
+
+// whether a rock thrown from `position` meets the hailstone at a non-negative whole time
+fn hits(position: Vector, velocity: Vector, h: &Hailstone) -> bool {
+    let zero = Rational::from(0);
+    let meet = || {
+        // offset == t * closing on every axis
+        let offset = difference(vector(h.position()), position)?;
+        let closing = difference(velocity, vector(h.velocity()))?;
+        let mut time = None;
+        for (o, c) in [
+            (offset.x, closing.x),
+            (offset.y, closing.y),
+            (offset.z, closing.z),
+        ] {
+            if c == zero {
+                if o != zero {
+                    return None;
+                }
+            } else {
+                let t = o.checked_div(c)?;
+                if time.is_some_and(|time| time != t) {
+                    return None;
+                }
+                time = Some(t);
+            }
+        }
+        time.or(Some(zero))
+    };
+    meet().is_some_and(|t| t.is_integer() && t >= zero)
+}
+
+// position and velocity of the rock hitting all three hailstones
+fn throw([origin, a, b]: [&Hailstone; 3]) -> Option<(Vector, Vector)> {
+    // seen from the first hailstone the rock passes through the origin, so its path lies in
+    // the plane spanned by the origin and the path of each other hailstone
+    let relative = |h: &Hailstone| {
+        Some((
+            difference(vector(h.position()), vector(origin.position()))?,
+            difference(vector(h.velocity()), vector(origin.velocity()))?,
+        ))
+    };
+    let (pa, va) = relative(a)?;
+    let (pb, vb) = relative(b)?;
+
+    // the largest products of the solver, about 1e35 for real inputs, see `test_magnitudes`
+    let direction = cross(cross(pa, va)?, cross(pb, vb)?)?;
+    let [x, y, z] = [direction.x, direction.y, direction.z].map(|c| c.to_integer());
+    let (x, y, z) = (x?, y?, z?);
+    let g = checked_gcd(checked_gcd(x, y)?, z)?;
+    if g == 0 {
+        return None;
     }
+    let direction = vector(Point3::new(x / g, y / g, z / g));
+
+    let ta = time_on_line(pa, va, direction)?;
+    let tb = time_on_line(pb, vb, direction)?;
 
-    solver.check();
-    let model = solver.get_model().unwrap();
-    let res = model.eval(&(&a + &b + &c), true).unwrap();
+    // position + t * velocity == hailstone position + t * hailstone velocity, on every axis
+    // for both hits
+    let one = Rational::from(1);
+    let times = vec![vec![one, ta], vec![one, tb]];
+    let axis = |a: i128, da: i128, b: i128, db: i128| {
+        let hit_a = Rational::from(a).checked_add(ta.checked_mul(da.into())?)?;
+        let hit_b = Rational::from(b).checked_add(tb.checked_mul(db.into())?)?;
+        let solution = linalg::solve(&times, &[hit_a, hit_b])?;
+        Some((solution[0], solution[1]))
+    };
 
-    res.as_u64()
+    let (x, dx) = axis(a.x, a.dx, b.x, b.dx)?;
+    let (y, dy) = axis(a.y, a.dy, b.y, b.dy)?;
+    let (z, dz) = axis(a.z, a.dz, b.z, b.dz)?;
+    Some((Point3::new(x, y, z), Point3::new(dx, dy, dz)))
+}
+
+// Subtracting `(P - pᵢ) x (V - vᵢ) == 0` for pairs of hailstones gives the textbook linear
+// system in the rock's `P` and `V`, but eliminating those six unknowns over `Rational<i128>`
+// builds products near 1e43 on real inputs. Finding the direction of the rock first keeps every
+// product within i128 and leaves only the 3x1 and 2x2 systems for `linalg`.
+pub fn part_two(input: &str) -> Option<i128> {
+    let hailstones = parse_input(input);
+
+    let (position, _) = hailstones
+        .windows(3)
+        .filter_map(|x| throw([&x[0], &x[1], &x[2]]))
+        .find(|&(position, velocity)| hailstones.iter().all(|h| hits(position, velocity, h)))?;
+
+    position
+        .x
+        .checked_add(position.y)?
+        .checked_add(position.z)?
+        .to_integer()
 }
 
 aoc::solution!(24);
@@ -80,9 +212,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = aoc::template::read_file("examples", 24);
-        let min = 7.0;
-        let max = 27.0;
-        assert_eq!(part_one_with_area(&input, min, max), 2);
+        assert_eq!(part_one_with_area(&input, 7, 27), 2);
     }
     #[test]
     fn test_part_two() {
@@ -91,4 +221,48 @@ mod tests {
             Some(47)
         );
     }
+
+    // hailstones around a rock with the magnitudes of real inputs times `scale`
+    fn generate(seed: u64, scale: i128) -> (String, i128) {
+        let mut state = seed;
+        let mut random = |low: i128, high: i128| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            low + (state as i128).rem_euclid(high - low)
+        };
+
+        let rock = [(); 3].map(|_| random(200_000_000_000_000, 400_000_000_000_000) * scale);
+        let speed = [(); 3].map(|_| random(-300, 300));
+        let mut input = String::new();
+        for _ in 0..300 {
+            let t = random(1_000_000_000, 1_000_000_000_000) * scale;
+            let v = [(); 3].map(|_| random(-1000, 1000));
+            let p = [0, 1, 2].map(|i| rock[i] + (speed[i] - v[i]) * t);
+            input += &format!(
+                "{}, {}, {} @ {}, {}, {}\n",
+                p[0], p[1], p[2], v[0], v[1], v[2]
+            );
+        }
+        (input, rock.iter().sum())
+    }
+    #[test]
+    fn test_magnitudes() {
+        // the first three hailstones fit in i128 even ten times larger than real inputs,
+        // beyond that `part_two` skips the triples that overflow
+        for seed in 1..=20 {
+            for scale in [1, 10] {
+                let (input, expected) = generate(seed, scale);
+                let hailstones = parse_input(&input);
+                assert!(throw([&hailstones[0], &hailstones[1], &hailstones[2]]).is_some());
+                assert_eq!(
+                    part_two(&input),
+                    Some(expected),
+                    "seed {seed} scale {scale}"
+                );
+            }
+        }
+        let (input, expected) = generate(1, 1000);
+        assert_eq!(part_two(&input), Some(expected));
+    }
 }
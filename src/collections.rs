@@ -0,0 +1,437 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasherDefault, Hash, Hasher},
+};
+
+pub type FastHashMap<K, V> = HashMap<K, V, BuildHasherDefault<FxHasher>>;
+pub type FastHashSet<T> = HashSet<T, BuildHasherDefault<FxHasher>>;
+
+/// The hasher rustc uses, a multiply and rotate per word.
+/// Much faster than SipHash on small keys and the same on every run, but easy to attack,
+/// which doesn't matter for puzzle inputs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, mut bytes: &[u8]) {
+        // the tail words alone can't tell "a" from "a\0", both end in the word 0x61, so the
+        // length goes in first
+        self.add(bytes.len() as u64);
+        while let Some((chunk, rest)) = bytes.split_first_chunk::<8>() {
+            self.add(u64::from_le_bytes(*chunk));
+            bytes = rest;
+        }
+        if let Some((chunk, rest)) = bytes.split_first_chunk::<4>() {
+            self.add(u32::from_le_bytes(*chunk).into());
+            bytes = rest;
+        }
+        if let Some((chunk, rest)) = bytes.split_first_chunk::<2>() {
+            self.add(u16::from_le_bytes(*chunk).into());
+            bytes = rest;
+        }
+        if let Some(&byte) = bytes.first() {
+            self.add(byte.into());
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(i.into());
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add(i.into());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(i.into());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+const BITS: usize = u64::BITS as usize;
+
+fn locate(i: usize) -> (usize, u64) {
+    (i / BITS, 1 << (i % BITS))
+}
+
+fn ones(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words.iter().enumerate().flat_map(|(i, &word)| {
+        let mut rest = word;
+        std::iter::from_fn(move || {
+            if rest == 0 {
+                return None;
+            }
+            let bit = rest.trailing_zeros() as usize;
+            rest &= rest - 1;
+            Some(i * BITS + bit)
+        })
+    })
+}
+
+fn count(words: &[u64]) -> usize {
+    words.iter().map(|x| x.count_ones() as usize).sum()
+}
+
+/// Set of integers below `64 * WORDS`, `Copy` and hashable so it fits into search states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedBitSet<const WORDS: usize = 1> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> Default for FixedBitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> FixedBitSet<WORDS> {
+    pub const CAPACITY: usize = WORDS * BITS;
+
+    pub const fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+
+    /// Returns whether the value was newly inserted, panics past the capacity.
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(value < Self::CAPACITY, "{value} doesn't fit in the set");
+        let (i, bit) = locate(value);
+        let new = self.words[i] & bit == 0;
+        self.words[i] |= bit;
+        new
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        if !self.contains(value) {
+            return false;
+        }
+        let (i, bit) = locate(value);
+        self.words[i] &= !bit;
+        true
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        let (i, bit) = locate(value);
+        self.words.get(i).is_some_and(|x| x & bit != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        count(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&x| x == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    /// Values in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        ones(&self.words)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut words = self.words;
+        for (a, b) in words.iter_mut().zip(other.words) {
+            *a |= b;
+        }
+        Self { words }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut words = self.words;
+        for (a, b) in words.iter_mut().zip(other.words) {
+            *a &= b;
+        }
+        Self { words }
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for FixedBitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for x in iter {
+            set.insert(x);
+        }
+        set
+    }
+}
+
+/// Set of integers that grows to fit the largest value inserted.
+#[derive(Debug, Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Room for values below `capacity` without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(BITS)],
+        }
+    }
+
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (i, bit) = locate(value);
+        if i >= self.words.len() {
+            self.words.resize(i + 1, 0);
+        }
+        let new = self.words[i] & bit == 0;
+        self.words[i] |= bit;
+        new
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        if !self.contains(value) {
+            return false;
+        }
+        let (i, bit) = locate(value);
+        self.words[i] &= !bit;
+        true
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        let (i, bit) = locate(value);
+        self.words.get(i).is_some_and(|x| x & bit != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        count(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&x| x == 0)
+    }
+
+    /// Removes every value but keeps the allocation.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Values in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        ones(&self.words)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let (long, short) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = long.words.clone();
+        for (a, b) in words.iter_mut().zip(&short.words) {
+            *a |= b;
+        }
+        Self { words }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let words = self
+            .words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| a & b)
+            .collect();
+        Self { words }
+    }
+
+    // the same set can be stored with any number of trailing empty words
+    fn trimmed(&self) -> &[u64] {
+        let end = self
+            .words
+            .iter()
+            .rposition(|&x| x != 0)
+            .map_or(0, |x| x + 1);
+        &self.words[..end]
+    }
+}
+
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed() == other.trimmed()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl Hash for BitSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.trimmed().hash(state);
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for x in iter {
+            set.insert(x);
+        }
+        set
+    }
+}
+
+/// Set of `(y, x)` positions inside a `height` by `width` rectangle, one bit per position.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    height: usize,
+    width: usize,
+    bits: BitSet,
+}
+
+impl BitGrid {
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            height,
+            width,
+            bits: BitSet::with_capacity(height * width),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns whether the position was newly inserted, panics outside the rectangle.
+    pub fn insert(&mut self, (y, x): (usize, usize)) -> bool {
+        assert!(
+            y < self.height && x < self.width,
+            "({y}, {x}) is outside of the grid"
+        );
+        self.bits.insert(y * self.width + x)
+    }
+
+    pub fn remove(&mut self, (y, x): (usize, usize)) -> bool {
+        y < self.height && x < self.width && self.bits.remove(y * self.width + x)
+    }
+
+    pub fn contains(&self, (y, x): (usize, usize)) -> bool {
+        y < self.height && x < self.width && self.bits.contains(y * self.width + x)
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// Positions in row major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.bits.iter().map(|i| (i / self.width, i % self.width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(value: impl Hash) -> u64 {
+        let mut hasher = FxHasher::default();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_hasher() {
+        assert_eq!(hash((1usize, 2usize)), hash((1usize, 2usize)));
+        assert_ne!(hash((1usize, 2usize)), hash((2usize, 1usize)));
+        assert_ne!(hash("ab"), hash("ab\0"));
+        assert_ne!(hash("a"), hash("a\0"));
+        assert_ne!(hash(b"a".as_slice()), hash(b"a\0".as_slice()));
+
+        let mut map: FastHashMap<&str, usize> = FastHashMap::default();
+        map.insert("broadcaster", 1);
+        *map.entry("broadcaster").or_default() += 1;
+        assert_eq!(map["broadcaster"], 2);
+
+        // unlike with RandomState, the same inserts iterate in the same order every time
+        let set: FastHashSet<u32> = (0..100).map(|x| x * 7919).collect();
+        let again: FastHashSet<u32> = (0..100).map(|x| x * 7919).collect();
+        assert!(set.iter().eq(again.iter()));
+    }
+
+    #[test]
+    fn test_fixed() {
+        let mut set = FixedBitSet::<2>::new();
+        assert!(set.insert(3) && set.insert(64) && set.insert(127));
+        assert!(!set.insert(64));
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(127) && !set.contains(128) && !set.contains(1000));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 64, 127]);
+        assert!(set.remove(3) && !set.remove(3));
+
+        let other: FixedBitSet<2> = [64, 100].into_iter().collect();
+        assert_eq!(set.union(&other).iter().collect::<Vec<_>>(), [64, 100, 127]);
+        assert_eq!(set.intersection(&other).iter().collect::<Vec<_>>(), [64]);
+        assert_eq!(FixedBitSet::<3>::CAPACITY, 192);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fixed_capacity() {
+        FixedBitSet::<1>::new().insert(64);
+    }
+
+    #[test]
+    fn test_growable() {
+        let mut set = BitSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(1000) && set.insert(5));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [5, 1000]);
+        set.remove(1000);
+        // equal despite the capacity left over from 1000
+        assert_eq!(set, BitSet::from_iter([5]));
+
+        let other: BitSet = [5, 6].into_iter().collect();
+        assert_eq!(set.union(&other), other);
+        assert_eq!(other.union(&set), other);
+        assert_eq!(set.intersection(&other), set);
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut grid = BitGrid::new(3, 4);
+        assert!(grid.insert((2, 3)) && grid.insert((0, 1)));
+        assert!(!grid.insert((2, 3)));
+        assert!(grid.contains((0, 1)) && !grid.contains((1, 0)) && !grid.contains((0, 4)));
+        assert_eq!(grid.iter().collect::<Vec<_>>(), [(0, 1), (2, 3)]);
+        assert!(grid.remove((0, 1)) && !grid.remove((5, 5)));
+        assert_eq!(grid.len(), 1);
+    }
+}
@@ -0,0 +1,171 @@
+use std::hash::Hash;
+
+use crate::collections::FastHashMap;
+
+/// The sequence is `start` steps long before it starts repeating every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Earliest step with the same state as step `n`.
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Brent's algorithm, only keeps two states around.
+/// Like `floyd` it never returns if the sequence doesn't repeat.
+pub fn brent<T: PartialEq + Clone>(initial: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = f(&initial);
+
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Floyd's tortoise and hare.
+pub fn floyd<T: PartialEq + Clone>(initial: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = f(&initial);
+    let mut hare = f(&tortoise);
+
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        hare = f(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Remembers every state, so `f` is called only once per step.
+/// Returns the cycle and the states of steps `0..start + period`.
+pub fn detect<T: Hash + Eq + Clone>(initial: T, mut f: impl FnMut(&T) -> T) -> (Cycle, Vec<T>) {
+    let mut seen = FastHashMap::default();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                period: states.len() - start,
+            };
+            return (cycle, states);
+        }
+
+        let next = f(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// State after `n` steps, using a cycle found by `brent` or `floyd`.
+pub fn advance<T>(initial: T, mut f: impl FnMut(&T) -> T, cycle: Cycle, n: usize) -> T {
+    (0..cycle.equivalent(n)).fold(initial, |state, _| f(&state))
+}
+
+/// State after `n` steps, skipping over repetitions.
+pub fn nth<T: Hash + Eq + Clone>(initial: T, f: impl FnMut(&T) -> T, n: usize) -> T {
+    let (cycle, mut states) = detect(initial, f);
+    states.swap_remove(cycle.equivalent(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 2, 3, ...
+    fn step(x: &u32) -> u32 {
+        if *x < 5 {
+            x + 1
+        } else {
+            2
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        let expected = Cycle {
+            start: 2,
+            period: 4,
+        };
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(floyd(0, step), expected);
+
+        let (cycle, states) = detect(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, [0, 1, 2, 3, 4, 5]);
+
+        assert_eq!(
+            floyd(7, |x| *x),
+            Cycle {
+                start: 0,
+                period: 1
+            }
+        );
+        assert_eq!(
+            brent(7, |x| *x),
+            Cycle {
+                start: 0,
+                period: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_advance() {
+        let cycle = brent(0, step);
+        assert_eq!(cycle.equivalent(1), 1);
+        assert_eq!(cycle.equivalent(9), 5);
+        assert_eq!(advance(0, step, cycle, 1_000_000_000), 4);
+        assert_eq!(nth(0, step, 1_000_000_000), 4);
+        assert_eq!(nth(0, step, 3), 3);
+    }
+}
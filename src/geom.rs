@@ -0,0 +1,360 @@
+pub mod polygon;
+
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// Point on a plane, fields are ordered `(y, x)` so points sort row by row like grid positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point2<T = isize> {
+    pub y: T,
+    pub x: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(y: T, x: T) -> Self {
+        Self { y, x }
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn manhattan(&self, other: &Self) -> T {
+        let abs_diff = |a: T, b: T| if a > b { a - b } else { b - a };
+        abs_diff(self.y, other.y) + abs_diff(self.x, other.x)
+    }
+}
+
+impl<T: Copy + From<i8> + Add<Output = T>> Point2<T> {
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction
+    }
+
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().map(move |d| self + d)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((y, x): (T, T)) -> Self {
+        Self { y, x }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(value: Point2<T>) -> Self {
+        (value.y, value.x)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.y + rhs.y, self.x + rhs.x)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.y - rhs.y, self.x - rhs.x)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.y * rhs, self.x * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.y, -self.x)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: From<i8> + Add<Output = T>> Add<Direction> for Point2<T> {
+    type Output = Self;
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+impl<T: Copy + From<i8> + Add<Output = T>> AddAssign<Direction> for Point2<T> {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.y, self.x)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point3<T = isize> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(value: Point3<T>) -> Self {
+        (value.x, value.y, value.z)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting with up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Down,
+            Down => Up,
+            Left => Right,
+            Right => Left,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    /// Unit step in this direction, with y growing downwards.
+    pub fn delta<T: From<i8>>(self) -> Point2<T> {
+        use Direction::*;
+        let (y, x) = match self {
+            Up => (-1, 0),
+            Right => (0, 1),
+            Down => (1, 0),
+            Left => (0, -1),
+        };
+        Point2::new(y.into(), x.into())
+    }
+}
+
+impl Neg for Direction {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        self.reverse()
+    }
+}
+
+impl<T: Copy + From<i8> + Mul<Output = T>> Mul<T> for Direction {
+    type Output = Point2<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        self.delta() * rhs
+    }
+}
+
+impl From<Direction> for (isize, isize) {
+    fn from(value: Direction) -> Self {
+        value.delta::<isize>().into()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirectionError;
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        use Direction::*;
+        Ok(match value {
+            'U' | 'u' | '^' | 'N' | 'n' => Up,
+            'R' | 'r' | '>' | 'E' | 'e' => Right,
+            'D' | 'd' | 'v' | 'S' | 's' => Down,
+            'L' | 'l' | '<' | 'W' | 'w' => Left,
+            _ => return Err(ParseDirectionError),
+        })
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => Err(ParseDirectionError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction() {
+        use Direction::*;
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), -d);
+            assert_eq!(d.delta::<isize>() + (-d).delta(), Point2::default());
+        }
+        assert_eq!(Up.turn_right(), Right);
+        assert_eq!(Up.turn_left(), Left);
+        assert_eq!(
+            ["U", "^", "N", "v", "<", "E"].map(|x| x.parse()),
+            [Ok(Up), Ok(Up), Ok(Up), Ok(Down), Ok(Left), Ok(Right)]
+        );
+        assert_eq!("X".parse::<Direction>(), Err(ParseDirectionError));
+        assert_eq!("UD".parse::<Direction>(), Err(ParseDirectionError));
+    }
+
+    #[test]
+    fn test_point2() {
+        let p = Point2::new(2, 3);
+        assert_eq!(p + Direction::Up, Point2::new(1, 3));
+        assert_eq!(p + Direction::Left * 3, Point2::new(2, 0));
+        assert_eq!(p - Point2::new(1, 1), Point2::new(1, 2));
+        assert_eq!(p.manhattan(&Point2::new(-1, 5)), 5);
+        assert_eq!(p.neighbours().count(), 4);
+        assert_eq!(<(isize, isize)>::from(Direction::Down), (1, 0));
+        assert_eq!(
+            Point2::<i128>::new(0, 0) + Direction::Right * 10,
+            Point2::new(0, 10)
+        );
+    }
+
+    #[test]
+    fn test_point3() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(4, 5, 6);
+        assert_eq!(a + b, Point3::new(5, 7, 9));
+        assert_eq!(b - a, Point3::new(3, 3, 3));
+        assert_eq!(a * 2, Point3::new(2, 4, 6));
+        assert_eq!(a.dot(&b), 32);
+        assert_eq!(a.cross(&b), Point3::new(-3, 6, -3));
+    }
+}
@@ -0,0 +1,133 @@
+use std::ops::Neg;
+
+use super::Point2;
+use crate::math::{gcd, Integer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+fn edges<T: Copy>(vertices: &[Point2<T>]) -> impl Iterator<Item = (Point2<T>, Point2<T>)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// Twice the area of a simple polygon with the shoelace formula, which keeps it an integer.
+/// Coordinates are signed, the edge vectors and the sum before `abs` can be negative.
+/// https://en.wikipedia.org/wiki/Shoelace_formula
+pub fn twice_area<T: Integer + Neg<Output = T>>(vertices: &[Point2<T>]) -> T {
+    edges(vertices)
+        .fold(T::ZERO, |acc, (a, b)| acc + a.x * b.y - b.x * a.y)
+        .abs()
+}
+
+/// Area of a simple polygon, rounded down for lattice polygons with half integer area.
+pub fn area<T: Integer + Neg<Output = T>>(vertices: &[Point2<T>]) -> T {
+    twice_area(vertices) / (T::ONE + T::ONE)
+}
+
+/// Number of lattice points on the edges.
+pub fn boundary_points<T: Integer + Neg<Output = T>>(vertices: &[Point2<T>]) -> T {
+    edges(vertices).fold(T::ZERO, |acc, (a, b)| acc + gcd(b.x - a.x, b.y - a.y))
+}
+
+/// Number of lattice points strictly inside, with Pick's theorem `A = I + B / 2 - 1`.
+/// https://en.wikipedia.org/wiki/Pick%27s_theorem
+pub fn interior_points<T: Integer + Neg<Output = T>>(vertices: &[Point2<T>]) -> T {
+    let two = T::ONE + T::ONE;
+    (twice_area(vertices) - boundary_points(vertices) + two) / two
+}
+
+/// Number of lattice points inside or on the boundary.
+pub fn lattice_points<T: Integer + Neg<Output = T>>(vertices: &[Point2<T>]) -> T {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// Where `point` lies relative to a simple polygon, exact for integer coordinates.
+pub fn locate<T: Integer + Neg<Output = T>>(vertices: &[Point2<T>], point: Point2<T>) -> Location {
+    let mut inside = false;
+
+    for (a, b) in edges(vertices) {
+        let cross = (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x);
+        let within = point.x >= a.x.min(b.x)
+            && point.x <= a.x.max(b.x)
+            && point.y >= a.y.min(b.y)
+            && point.y <= a.y.max(b.y);
+        if cross == T::ZERO && within {
+            return Location::Boundary;
+        }
+
+        // count crossings of a ray going right, edges include their lower end only
+        if (a.y > point.y) != (b.y > point.y) {
+            // point is left of the edge if the cross product has the sign of the edge direction
+            let left = if b.y > a.y {
+                cross > T::ZERO
+            } else {
+                cross < T::ZERO
+            };
+            if left {
+                inside = !inside;
+            }
+        }
+    }
+
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: i128) -> Vec<Point2<i128>> {
+        [(0, 0), (0, size), (size, size), (size, 0)]
+            .map(Point2::from)
+            .to_vec()
+    }
+
+    #[test]
+    fn test_area() {
+        let s = square(4);
+        assert_eq!(twice_area(&s), 32);
+        assert_eq!(area(&s), 16);
+        assert_eq!(boundary_points(&s), 16);
+        assert_eq!(interior_points(&s), 9);
+        assert_eq!(lattice_points(&s), 25);
+
+        let triangle = [(0, 0), (0, 1), (1, 0)].map(Point2::<i64>::from);
+        assert_eq!(twice_area(&triangle), 1);
+        assert_eq!(interior_points(&triangle), 0);
+        assert_eq!(lattice_points(&triangle), 3);
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        let s = square(1 << 40);
+        assert_eq!(area(&s), 1 << 80);
+        assert_eq!(lattice_points(&s), ((1 << 40) + 1) * ((1 << 40) + 1));
+    }
+
+    #[test]
+    fn test_locate() {
+        // an L shape
+        let l = [(0, 0), (0, 2), (1, 2), (1, 1), (3, 1), (3, 0)].map(Point2::<i32>::from);
+        assert_eq!(locate(&l, Point2::new(0, 1)), Location::Boundary);
+        assert_eq!(locate(&l, Point2::new(2, 1)), Location::Boundary);
+        assert_eq!(locate(&l, Point2::new(2, 2)), Location::Outside);
+        assert_eq!(locate(&l, Point2::new(-1, 0)), Location::Outside);
+        assert_eq!(locate(&l, Point2::new(4, 0)), Location::Outside);
+
+        let s = square(4).iter().map(|p| *p * 2).collect::<Vec<_>>();
+        assert_eq!(locate(&s, Point2::new(4, 4)), Location::Inside);
+        assert_eq!(locate(&s, Point2::new(1, 7)), Location::Inside);
+        assert_eq!(locate(&s, Point2::new(9, 4)), Location::Outside);
+    }
+}
@@ -0,0 +1,583 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    fmt::{Display, Write},
+    hash::Hash,
+    ops::{Add, Sub},
+};
+
+use crate::collections::{BitSet, FastHashMap, FastHashSet};
+
+/// Adjacency list graph, nodes are interned from labels and referred to by index.
+#[derive(Debug, Clone)]
+pub struct Graph<L = String, W = usize> {
+    labels: Vec<L>,
+    indices: FastHashMap<L, usize>,
+    edges: Vec<Vec<(usize, W)>>,
+}
+
+impl<L, W> Default for Graph<L, W> {
+    fn default() -> Self {
+        Self {
+            labels: Vec::new(),
+            indices: FastHashMap::default(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<L: Hash + Eq + Clone, W: Copy> Graph<L, W> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of the node with this label, adding it if it doesn't exist yet.
+    pub fn node(&mut self, label: L) -> usize {
+        if let Some(&index) = self.indices.get(&label) {
+            return index;
+        }
+
+        let index = self.labels.len();
+        self.indices.insert(label.clone(), index);
+        self.labels.push(label);
+        self.edges.push(Vec::new());
+        index
+    }
+
+    pub fn index(&self, label: &L) -> Option<usize> {
+        self.indices.get(label).copied()
+    }
+
+    pub fn label(&self, index: usize) -> &L {
+        &self.labels[index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, weight: W) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn remove_edge(&mut self, from: usize, to: usize) {
+        self.edges[from].retain(|&(n, _)| n != to);
+    }
+
+    pub fn neighbours(&self, index: usize) -> &[(usize, W)] {
+        &self.edges[index]
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, W)> + '_ {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(|(from, x)| x.iter().map(move |&(to, w)| (from, to, w)))
+    }
+
+    /// Nodes reachable from each other when edges are followed in both directions.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut undirected = vec![Vec::new(); self.len()];
+        for (from, to, _) in self.edges() {
+            undirected[from].push(to);
+            undirected[to].push(from);
+        }
+
+        let mut seen = vec![false; self.len()];
+        let mut components = Vec::new();
+
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+
+            let component = dfs(start, |&n| {
+                undirected[n]
+                    .iter()
+                    .copied()
+                    .filter(|&x| !seen[x])
+                    .collect::<Vec<_>>()
+            });
+            for &n in component.iter() {
+                seen[n] = true;
+            }
+            components.push(component);
+        }
+
+        components
+    }
+
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<(W, Vec<usize>)>
+    where
+        W: Ord + Default + Add<Output = W>,
+    {
+        dijkstra([from], |&n| self.edges[n].clone(), |&n| n == to)
+    }
+
+    /// Longest path visiting every node at most once, by exhaustive search.
+    pub fn longest_path(&self, from: usize, to: usize) -> Option<W>
+    where
+        W: Ord + Default + Add<Output = W>,
+    {
+        let mut visited = BitSet::with_capacity(self.len());
+        let mut best = None;
+        self.longest_path_from(from, to, W::default(), &mut visited, &mut best);
+        best
+    }
+
+    fn longest_path_from(
+        &self,
+        at: usize,
+        to: usize,
+        length: W,
+        visited: &mut BitSet,
+        best: &mut Option<W>,
+    ) where
+        W: Ord + Default + Add<Output = W>,
+    {
+        if at == to {
+            if best.is_none_or(|x| x < length) {
+                *best = Some(length);
+            }
+            return;
+        }
+
+        visited.insert(at);
+        for &(n, w) in self.edges[at].iter() {
+            if !visited.contains(n) {
+                self.longest_path_from(n, to, length + w, visited, best);
+            }
+        }
+        visited.remove(at);
+    }
+
+    /// Maximum flow from `source` to `sink` using edge weights as capacities, with the nodes on
+    /// the source side of a minimum cut. Gives up once the flow reaches `limit`, the side is
+    /// left empty then.
+    pub fn min_cut_between(&self, source: usize, sink: usize, limit: Option<W>) -> (W, Vec<usize>)
+    where
+        W: Ord + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        Residual::new(self.len(), self.edges()).min_cut(source, sink, limit)
+    }
+
+    /// Lightest set of edges whose removal splits the graph, as their total weight and the nodes
+    /// on one side. Edges have to go both ways, `None` for fewer than two nodes.
+    pub fn min_cut(&self) -> Option<(W, Vec<usize>)>
+    where
+        W: Ord + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        let mut residual = Residual::new(self.len(), self.edges());
+        // node 0 is on one side of the cut, so some other node is on the other
+        let mut best: Option<(W, Vec<usize>)> = None;
+        for sink in 1..self.len() {
+            let (flow, side) = residual.min_cut(0, sink, best.as_ref().map(|x| x.0));
+            if best.as_ref().is_none_or(|x| flow < x.0) {
+                best = Some((flow, side));
+            }
+        }
+        best
+    }
+
+    /// Graphviz source of the graph, `node` gives the attributes of every node like
+    /// `label="a" shape=box`. Edges with a twin of the same weight going back are drawn once
+    /// without arrows, with `weights` every edge is labelled with its weight.
+    pub fn to_dot(&self, weights: bool, node: impl Fn(usize, &L) -> String) -> String
+    where
+        W: PartialEq + Display,
+    {
+        let mut dot = String::from("digraph {\n");
+
+        for (index, label) in self.labels.iter().enumerate() {
+            writeln!(dot, "    {index} [{}];", node(index, label)).unwrap();
+        }
+
+        for (from, to, w) in self.edges() {
+            let twin = from != to && self.edges[to].iter().any(|&(n, x)| n == from && x == w);
+            if twin && from > to {
+                continue;
+            }
+
+            let mut attributes = Vec::new();
+            if twin {
+                attributes.push("dir=none".to_string());
+            }
+            if weights {
+                attributes.push(format!("label=\"{w}\""));
+            }
+
+            if attributes.is_empty() {
+                writeln!(dot, "    {from} -> {to};").unwrap();
+            } else {
+                writeln!(dot, "    {from} -> {to} [{}];", attributes.join(" ")).unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// residual network for max flow, arc i ^ 1 is the reverse of arc i
+struct Residual<W> {
+    to: Vec<usize>,
+    initial: Vec<W>,
+    capacity: Vec<W>,
+    arcs: Vec<Vec<usize>>,
+}
+
+impl<W> Residual<W>
+where
+    W: Copy + Ord + Default + Add<Output = W> + Sub<Output = W>,
+{
+    fn new(len: usize, edges: impl Iterator<Item = (usize, usize, W)>) -> Self {
+        let mut to = Vec::new();
+        let mut initial = Vec::new();
+        let mut arcs = vec![Vec::new(); len];
+        for (from, n, w) in edges {
+            arcs[from].push(to.len());
+            to.push(n);
+            initial.push(w);
+            arcs[n].push(to.len());
+            to.push(from);
+            initial.push(W::default());
+        }
+
+        Self {
+            to,
+            capacity: initial.clone(),
+            initial,
+            arcs,
+        }
+    }
+
+    // Edmonds-Karp, augments along shortest paths
+    fn min_cut(&mut self, source: usize, sink: usize, limit: Option<W>) -> (W, Vec<usize>) {
+        let zero = W::default();
+        self.capacity.copy_from_slice(&self.initial);
+
+        let mut flow = zero;
+        let mut parent = vec![None; self.arcs.len()];
+        let mut reached = BitSet::with_capacity(self.arcs.len());
+        let mut queue = VecDeque::new();
+        let mut path = Vec::new();
+
+        loop {
+            reached.clear();
+            reached.insert(source);
+            queue.push_back(source);
+
+            // no need to look further once there is a path
+            while let Some(at) = queue.pop_front().filter(|_| !reached.contains(sink)) {
+                for &arc in self.arcs[at].iter() {
+                    if self.capacity[arc] > zero && reached.insert(self.to[arc]) {
+                        parent[self.to[arc]] = Some(arc);
+                        queue.push_back(self.to[arc]);
+                    }
+                }
+            }
+            queue.clear();
+
+            if !reached.contains(sink) {
+                return (flow, reached.iter().collect());
+            }
+
+            path.clear();
+            let mut at = sink;
+            while at != source {
+                let arc = parent[at].expect("reached nodes have a parent");
+                path.push(arc);
+                at = self.to[arc ^ 1];
+            }
+
+            let bottleneck = path.iter().map(|&arc| self.capacity[arc]).min();
+            let bottleneck = bottleneck.unwrap_or(zero);
+            for &arc in path.iter() {
+                self.capacity[arc] = self.capacity[arc] - bottleneck;
+                self.capacity[arc ^ 1] = self.capacity[arc ^ 1] + bottleneck;
+            }
+
+            flow = flow + bottleneck;
+            if limit.is_some_and(|x| flow >= x) {
+                return (flow, Vec::new());
+            }
+        }
+    }
+}
+
+/// Cheapest path from any of `starts` to a node satisfying `is_goal`.
+pub fn dijkstra<N, W, S>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> S,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(W, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    W: Copy + Ord + Default + Add<Output = W>,
+    S: IntoIterator<Item = (N, W)>,
+{
+    astar(starts, successors, |_| W::default(), is_goal)
+}
+
+/// Like `dijkstra`, `heuristic` must never overestimate the remaining cost.
+pub fn astar<N, W, S>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> S,
+    mut heuristic: impl FnMut(&N) -> W,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(W, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    W: Copy + Ord + Default + Add<Output = W>,
+    S: IntoIterator<Item = (N, W)>,
+{
+    // nodes are stored once and referred to by index in the heap
+    let mut nodes: Vec<N> = Vec::new();
+    let mut indices: FastHashMap<N, usize> = FastHashMap::default();
+    // (cost, parent) per index
+    let mut best: Vec<(W, Option<usize>)> = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if indices.contains_key(&start) {
+            continue;
+        }
+        let index = nodes.len();
+        heap.push(Reverse((heuristic(&start), W::default(), index)));
+        indices.insert(start.clone(), index);
+        nodes.push(start);
+        best.push((W::default(), None));
+    }
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        if cost > best[index].0 {
+            continue;
+        }
+
+        if is_goal(&nodes[index]) {
+            let mut path = vec![nodes[index].clone()];
+            let mut at = index;
+            while let Some(parent) = best[at].1 {
+                path.push(nodes[parent].clone());
+                at = parent;
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+
+        for (n, w) in successors(&nodes[index]) {
+            let new_cost = cost + w;
+            let n_index = match indices.get(&n) {
+                Some(&i) if best[i].0 <= new_cost => continue,
+                Some(&i) => i,
+                None => {
+                    let i = nodes.len();
+                    indices.insert(n.clone(), i);
+                    nodes.push(n);
+                    best.push((new_cost, None));
+                    i
+                }
+            };
+
+            best[n_index] = (new_cost, Some(index));
+            heap.push(Reverse((
+                new_cost + heuristic(&nodes[n_index]),
+                new_cost,
+                n_index,
+            )));
+        }
+    }
+
+    None
+}
+
+/// Number of steps to every node reachable from `start`.
+pub fn bfs<N, S>(start: N, mut successors: impl FnMut(&N) -> S) -> FastHashMap<N, usize>
+where
+    N: Hash + Eq + Clone,
+    S: IntoIterator<Item = N>,
+{
+    let mut distances = FastHashMap::from_iter([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        for n in successors(&node) {
+            if !distances.contains_key(&n) {
+                distances.insert(n.clone(), steps + 1);
+                queue.push_back((n, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Every node reachable from `start`, in depth first order.
+pub fn dfs<N, S>(start: N, mut successors: impl FnMut(&N) -> S) -> Vec<N>
+where
+    N: Hash + Eq + Clone,
+    S: IntoIterator<Item = N>,
+{
+    let mut visited = FastHashSet::default();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        let mut next: Vec<_> = successors(&node)
+            .into_iter()
+            .filter(|x| !visited.contains(x))
+            .collect();
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        for (a, b, w) in [
+            ("a", "b", 7),
+            ("a", "c", 9),
+            ("a", "f", 14),
+            ("b", "c", 10),
+            ("b", "d", 15),
+            ("c", "d", 11),
+            ("c", "f", 2),
+            ("d", "e", 6),
+            ("e", "f", 9),
+        ] {
+            let (a, b) = (graph.node(a), graph.node(b));
+            graph.add_undirected_edge(a, b, w);
+        }
+        graph.node("x");
+        graph
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let graph = example();
+        let (a, e) = (graph.index(&"a").unwrap(), graph.index(&"e").unwrap());
+        let (cost, path) = graph.shortest_path(a, e).unwrap();
+        assert_eq!(cost, 20);
+        assert_eq!(
+            path.into_iter()
+                .map(|x| *graph.label(x))
+                .collect::<Vec<_>>(),
+            ["a", "c", "f", "e"]
+        );
+        assert_eq!(graph.shortest_path(a, graph.index(&"x").unwrap()), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let target = (3i32, 4i32);
+        let successors = |&(y, x): &(i32, i32)| {
+            [(y + 1, x), (y, x + 1), (y - 1, x), (y, x - 1)]
+                .into_iter()
+                .filter(|&(y, x)| (0..5).contains(&y) && (0..5).contains(&x) && (y, x) != (1, 1))
+                .map(|n| (n, 1))
+        };
+        let heuristic = |&(y, x): &(i32, i32)| (target.0 - y).abs() + (target.1 - x).abs();
+
+        let (cost, path) = astar([(0, 0)], successors, heuristic, |&n| n == target).unwrap();
+        assert_eq!(cost, 7);
+        assert_eq!(path.len(), 8);
+        assert_eq!(
+            dijkstra([(0, 0)], successors, |&n| n == target).map(|x| x.0),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn test_traversal() {
+        let graph = example();
+        assert_eq!(
+            graph.components().iter().map(Vec::len).collect::<Vec<_>>(),
+            [6, 1]
+        );
+        assert_eq!(bfs(0u32, |&x| (x < 5).then_some(x + 2))[&6], 3);
+        assert_eq!(
+            dfs(1u32, |&x| [x * 2, x * 3].into_iter().filter(|&x| x < 10)),
+            [1, 2, 4, 8, 6, 3, 9]
+        );
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let mut graph: Graph<u32> = Graph::new();
+        // two triangles joined by a single edge, and a double edge inside the first
+        for (a, b, w) in [
+            (0, 1, 2),
+            (1, 2, 1),
+            (2, 0, 1),
+            (3, 4, 1),
+            (4, 5, 1),
+            (5, 3, 1),
+        ] {
+            let (a, b) = (graph.node(a), graph.node(b));
+            graph.add_undirected_edge(a, b, w);
+        }
+        let (a, b) = (graph.node(2), graph.node(3));
+        graph.add_undirected_edge(a, b, 1);
+
+        let (flow, side) = graph.min_cut_between(0, 5, None);
+        assert_eq!((flow, side), (1, vec![0, 1, 2]));
+        assert_eq!(graph.min_cut_between(0, 1, None).0, 3);
+        assert_eq!(graph.min_cut().map(|x| x.0), Some(1));
+
+        let (cost, mut side) = example().min_cut().unwrap();
+        side.sort();
+        // the isolated node costs nothing to cut off
+        assert_eq!((cost, side), (0, vec![0, 1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn test_dot() {
+        let mut graph: Graph<&str> = Graph::new();
+        let (a, b, c) = (graph.node("a"), graph.node("b"), graph.node("c"));
+        graph.add_undirected_edge(a, b, 2);
+        graph.add_edge(b, c, 3);
+
+        let dot = graph.to_dot(true, |i, label| {
+            let shape = if i == c { "box" } else { "ellipse" };
+            format!("label={label:?} shape={shape}")
+        });
+        assert_eq!(
+            dot,
+            "digraph {\n    \
+             0 [label=\"a\" shape=ellipse];\n    \
+             1 [label=\"b\" shape=ellipse];\n    \
+             2 [label=\"c\" shape=box];\n    \
+             0 -> 1 [dir=none label=\"2\"];\n    \
+             1 -> 2 [label=\"3\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_longest_path() {
+        let graph = example();
+        let (a, e) = (graph.index(&"a").unwrap(), graph.index(&"e").unwrap());
+        // a - b - c - f - e is 28, a - f - c - b - d - e is 47
+        assert_eq!(graph.longest_path(a, e), Some(47));
+    }
+}
@@ -0,0 +1,392 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+    slice::Chunks,
+    str::FromStr,
+};
+
+const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    Width {
+        line: usize,
+    },
+    Cell {
+        line: usize,
+        column: usize,
+        value: char,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Width { line } => {
+                write!(f, "line {} has a different width than the first", line + 1)
+            }
+            ParseGridError::Cell {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "line {}, column {}: unexpected {value:?}",
+                line + 1,
+                column + 1
+            ),
+        }
+    }
+}
+
+/// Row-major 2D grid, positions are `(y, x)` like everywhere else in the solutions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![value; height * width],
+            height,
+            width,
+        }
+    }
+
+    pub fn from_vec(height: usize, width: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), height * width, "data does not fit grid size");
+        Self {
+            data,
+            height,
+            width,
+        }
+    }
+
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        Self::try_parse_with(input, |c| Some(f(c)))
+    }
+
+    /// Like `parse_with`, but `f` can reject characters that are not valid cells.
+    pub fn try_parse_with(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let mut data = Vec::with_capacity(input.len());
+        let mut height = 0;
+        let mut width = 0;
+
+        for (line, row) in input.lines().enumerate() {
+            let before = data.len();
+            for (column, value) in row.chars().enumerate() {
+                let cell = f(value).ok_or(ParseGridError::Cell {
+                    line,
+                    column,
+                    value,
+                })?;
+                data.push(cell);
+            }
+
+            if line == 0 {
+                width = data.len();
+            } else if data.len() - before != width {
+                return Err(ParseGridError::Width { line });
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            data,
+            height,
+            width,
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, (y, x): (usize, usize)) -> bool {
+        y < self.height && x < self.width
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.data[position.0 * self.width + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.data[position.0 * self.width + position.1])
+    }
+
+    /// Converts signed coordinates to a position, if they are inside the grid.
+    pub fn position(&self, y: isize, x: isize) -> Option<(usize, usize)> {
+        let position = (y.try_into().ok()?, x.try_into().ok()?);
+        self.contains(position).then_some(position)
+    }
+
+    pub fn offset(
+        &self,
+        (y, x): (usize, usize),
+        (dy, dx): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let position = (y.checked_add_signed(dy)?, x.checked_add_signed(dx)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// Orthogonal neighbours inside the grid, clockwise starting with up.
+    pub fn neighbours(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |d| self.offset(position, d))
+    }
+
+    /// Orthogonal and diagonal neighbours inside the grid, clockwise starting with up-left.
+    pub fn neighbours8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |d| self.offset(position, d))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (y, x)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, x)| *x == value).map(|(p, _)| p)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> Chunks<'_, T> {
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        for position in [a, b] {
+            assert!(
+                self.contains(position),
+                "position {position:?} out of bounds"
+            );
+        }
+        self.data
+            .swap(a.0 * self.width + a.1, b.0 * self.width + b.1);
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+
+    fn remap(&self, height: usize, width: usize, f: impl Fn(usize, usize) -> (usize, usize)) -> Self
+    where
+        T: Clone,
+    {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (y, x)))
+            .map(|(y, x)| self[f(y, x)].clone())
+            .collect();
+
+        Self {
+            data,
+            height,
+            width,
+        }
+    }
+
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.width, self.height, |y, x| (x, y))
+    }
+
+    pub fn rotated_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.width, self.height, |y, x| (self.height - 1 - x, y))
+    }
+
+    pub fn rotated_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.width, self.height, |y, x| (x, self.width - 1 - y))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    fn index(&self, (y, x): (usize, usize)) -> &Self::Output {
+        assert!(self.contains((y, x)), "position {:?} out of bounds", (y, x));
+        &self.data[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (y, x): (usize, usize)) -> &mut Self::Output {
+        assert!(self.contains((y, x)), "position {:?} out of bounds", (y, x));
+        &mut self.data[y * self.width + x]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| c)
+    }
+}
+
+impl FromStr for Grid<u8> {
+    type Err = ParseGridError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse_with(s, |c| c.is_ascii().then_some(c as u8))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for value in row {
+                write!(f, "{value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "S.#\n..#";
+
+    #[test]
+    fn test_parse_display() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[(0, 2)], '#');
+        assert_eq!(grid.find(&'S'), Some((0, 0)));
+        assert_eq!(grid.to_string(), INPUT);
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(ParseGridError::Width { line: 1 })
+        );
+        let digits = Grid::try_parse_with("12\n3x", |c| c.to_digit(10));
+        assert_eq!(
+            digits,
+            Err(ParseGridError::Cell {
+                line: 1,
+                column: 1,
+                value: 'x'
+            })
+        );
+        assert_eq!(
+            digits.unwrap_err().to_string(),
+            "line 2, column 2: unexpected 'x'"
+        );
+        assert_eq!(
+            "ab\ncé".parse::<Grid<u8>>(),
+            Err(ParseGridError::Cell {
+                line: 1,
+                column: 1,
+                value: 'é'
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "position (0, 3) out of bounds")]
+    fn test_swap_out_of_bounds() {
+        // (0, 3) is still inside the buffer, at (1, 0)
+        let mut grid: Grid<u8> = INPUT.parse().unwrap();
+        grid.swap((0, 0), (0, 3));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid: Grid<u8> = INPUT.parse().unwrap();
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours8((1, 1)).collect::<Vec<_>>(),
+            [(0, 0), (0, 1), (0, 2), (1, 2), (1, 0)]
+        );
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.position(1, 2), Some((1, 2)));
+        assert_eq!(grid.position(-1, 2), None);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['S', '.', '#'], ['.', '.', '#']]
+        );
+        assert_eq!(grid.column(2).collect::<String>(), "##");
+        assert_eq!(grid.transposed().to_string(), "S.\n..\n##");
+        assert_eq!(grid.rotated_clockwise().to_string(), ".S\n..\n##");
+        assert_eq!(grid.rotated_counterclockwise().to_string(), "##\n..\nS.");
+        assert_eq!(grid.rotated_clockwise().rotated_counterclockwise(), grid);
+    }
+}
@@ -0,0 +1,278 @@
+pub mod linalg;
+pub mod poly;
+pub mod rational;
+
+use std::{
+    fmt::Debug,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    /// `None` for the minimum of a signed type.
+    fn checked_abs(self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// `None` for negative values.
+    fn to_u128(self) -> Option<u128>;
+}
+
+macro_rules! impl_integer {
+    (unsigned: $($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                self
+            }
+            fn checked_abs(self) -> Option<Self> {
+                Some(self)
+            }
+            fn checked_neg(self) -> Option<Self> {
+                <$t>::checked_neg(self)
+            }
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+            fn to_u128(self) -> Option<u128> {
+                Some(self as u128)
+            }
+        })*
+    };
+    (signed: $($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+            fn checked_abs(self) -> Option<Self> {
+                <$t>::checked_abs(self)
+            }
+            fn checked_neg(self) -> Option<Self> {
+                <$t>::checked_neg(self)
+            }
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+            fn to_u128(self) -> Option<u128> {
+                self.try_into().ok()
+            }
+        })*
+    };
+}
+
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_integer!(signed: i8, i16, i32, i64, i128, isize);
+
+/// Greatest common divisor, always non-negative and `gcd(0, 0) == 0`.
+pub fn gcd<T: Integer>(first: T, second: T) -> T {
+    checked_gcd(first, second).expect("gcd overflowed")
+}
+
+/// `None` only if the gcd is the absolute value of a signed minimum, like `gcd(i8::MIN, 0)`.
+pub fn checked_gcd<T: Integer>(first: T, second: T) -> Option<T> {
+    // one remainder step brings a minimum into range, the other value is positive then
+    let (mut a, mut b) = match (first.checked_abs(), second.checked_abs()) {
+        (Some(a), Some(b)) => (a, b),
+        (None, Some(b)) if b != T::ZERO => (b, (first % b).abs()),
+        (Some(a), None) if a != T::ZERO => (a, (second % a).abs()),
+        _ => return None,
+    };
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    Some(a)
+}
+
+/// Least common multiple, dividing before multiplying so only a result that doesn't fit overflows.
+pub fn lcm<T: Integer>(first: T, second: T) -> T {
+    checked_lcm(first, second).expect("lcm overflowed")
+}
+
+pub fn checked_lcm<T: Integer>(first: T, second: T) -> Option<T> {
+    if first == T::ZERO || second == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (first.checked_abs()? / checked_gcd(first, second)?).checked_mul(second.checked_abs()?)
+}
+
+/// Least common multiple of all values computed in `u128`, `None` on negatives or overflow.
+pub fn lcm_u128<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<u128> {
+    values
+        .into_iter()
+        .try_fold(1, |acc, x| checked_lcm(acc, x.to_u128()?))
+}
+
+/// Largest `r` with `r * r <= n`, panics on negative `n`.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    assert!(n >= T::ZERO, "square root of a negative number");
+    let two = T::ONE + T::ONE;
+    if n < two {
+        return n;
+    }
+
+    // Newton's method from above, n / 2 + 1 is never below the root and can't overflow
+    let mut x = n / two + T::ONE;
+    let mut y = (x + n / x) / two;
+    while y < x {
+        x = y;
+        y = (x + n / x) / two;
+    }
+    x
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd<T: Integer + Neg<Output = T>>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let (mut old_t, mut t) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Euclidean remainder, always in `0..modulus` for a positive modulus.
+pub fn rem_euclid<T: Integer>(a: T, modulus: T) -> T {
+    let r = a % modulus;
+    if r < T::ZERO {
+        r + modulus.abs()
+    } else {
+        r
+    }
+}
+
+/// `x` in `0..modulus` with `a * x == 1 (mod modulus)`, if `a` and `modulus` are coprime.
+pub fn mod_inverse<T: Integer + Neg<Output = T>>(a: T, modulus: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(rem_euclid(a, modulus), modulus);
+    (g == T::ONE).then(|| rem_euclid(x, modulus))
+}
+
+/// Solves `x == residue (mod modulus)` for every pair, moduli must be positive but don't need to
+/// be coprime. Returns the smallest non-negative solution and the combined modulus,
+/// `None` if a modulus isn't positive, the congruences contradict each other or the modulus
+/// overflows.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            if m2 <= 0 {
+                return None;
+            }
+            let r2 = rem_euclid(r2, m2);
+            let (g, p, _) = extended_gcd(m1, m2);
+            if (r2 - r1) % g != 0 {
+                return None;
+            }
+
+            let step = m2 / g;
+            let k = rem_euclid(((r2 - r1) / g % step).checked_mul(p % step)?, step);
+            let modulus = m1.checked_mul(step)?;
+            Some((rem_euclid(r1 + m1.checked_mul(k)?, modulus), modulus))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(7usize, 0), 7);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4usize, 6), 12);
+        assert_eq!(lcm(0usize, 6), 0);
+        // the product overflows but the result fits
+        assert_eq!(lcm(u64::MAX, u64::MAX), u64::MAX);
+        assert_eq!(checked_lcm(u8::MAX, 2), None);
+        assert_eq!(
+            lcm_u128([u32::MAX, u32::MAX - 1]),
+            Some(18446744060824649730)
+        );
+        assert_eq!(lcm_u128([3i32, -4]), None);
+    }
+
+    #[test]
+    fn test_gcd_signed_min() {
+        assert_eq!(gcd(i8::MIN, 6), 2);
+        assert_eq!(gcd(12, i64::MIN), 4);
+        assert_eq!(gcd(i128::MIN, -1), 1);
+        assert_eq!(gcd(i8::MIN, i8::MIN / 2), 64);
+        assert_eq!(checked_gcd(i8::MIN, 0), None);
+        assert_eq!(checked_gcd(i32::MIN, i32::MIN), None);
+        assert_eq!(checked_lcm(i8::MIN, 3), None);
+        assert_eq!(checked_lcm(i8::MIN / 2, 2), Some(64));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0u32), 0);
+        assert_eq!(isqrt(1u32), 1);
+        assert_eq!(isqrt(15u32), 3);
+        assert_eq!(isqrt(16u32), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(i128::MAX), 13043817825332782212);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let (g, x, y) = extended_gcd(240i64, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(4i64, 8), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        // cycles of 3 and 5 steps reaching the target after 1 and 4 steps
+        assert_eq!(crt([(1, 3), (4, 5)]), Some((4, 15)));
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([(1, 3), (2, -5)]), None);
+    }
+}
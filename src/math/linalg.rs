@@ -0,0 +1,126 @@
+use std::ops::Neg;
+
+use super::{rational::Rational, Integer};
+
+pub type Matrix<T = i128> = Vec<Vec<Rational<T>>>;
+
+/// Matrix of rationals from rows of integers.
+pub fn matrix<T, const N: usize>(rows: impl IntoIterator<Item = [T; N]>) -> Matrix<T>
+where
+    T: Integer + Neg<Output = T>,
+{
+    rows.into_iter()
+        .map(|row| row.into_iter().map(Rational::from).collect())
+        .collect()
+}
+
+/// Gauss-Jordan elimination, leaves `matrix` in reduced row echelon form and returns its rank.
+/// Panics on overflow.
+pub fn row_reduce<T: Integer + Neg<Output = T>>(matrix: &mut [Vec<Rational<T>>]) -> usize {
+    checked_row_reduce(matrix).expect("rational overflow")
+}
+
+/// `row_reduce` returning `None` on overflow, which leaves `matrix` partially reduced.
+pub fn checked_row_reduce<T: Integer + Neg<Output = T>>(
+    matrix: &mut [Vec<Rational<T>>],
+) -> Option<usize> {
+    let zero = Rational::from(T::ZERO);
+    let columns = matrix.first().map_or(0, Vec::len);
+    let mut rank = 0;
+
+    for column in 0..columns {
+        if rank == matrix.len() {
+            break;
+        }
+        let Some(pivot) = (rank..matrix.len()).find(|&r| matrix[r][column] != zero) else {
+            continue;
+        };
+
+        matrix.swap(rank, pivot);
+        let p = matrix[rank][column];
+        for x in matrix[rank][column..].iter_mut() {
+            *x = x.checked_div(p)?;
+        }
+
+        let pivot_row = matrix[rank].clone();
+        for (r, row) in matrix.iter_mut().enumerate() {
+            let factor = row[column];
+            if r == rank || factor == zero {
+                continue;
+            }
+            // everything left of the pivot is already zero in the pivot row
+            for (x, p) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                *x = x.checked_sub(factor.checked_mul(*p)?)?;
+            }
+        }
+
+        rank += 1;
+    }
+
+    Some(rank)
+}
+
+/// The only `x` with `a * x == b`, `None` if there is no solution, more than one or the
+/// elimination overflows.
+/// `a` can have more rows than columns as long as the extra equations agree.
+pub fn solve<T: Integer + Neg<Output = T>>(
+    a: &[Vec<Rational<T>>],
+    b: &[Rational<T>],
+) -> Option<Vec<Rational<T>>> {
+    assert_eq!(a.len(), b.len(), "one right hand side per equation");
+    let n = a.first()?.len();
+
+    let mut augmented: Matrix<T> = a
+        .iter()
+        .zip(b)
+        .map(|(row, x)| row.iter().chain([x]).copied().collect())
+        .collect();
+
+    // a unique solution has a pivot on the diagonal of every column but the last
+    let one = Rational::from(T::ONE);
+    if checked_row_reduce(&mut augmented)? != n || (0..n).any(|i| augmented[i][i] != one) {
+        return None;
+    }
+
+    Some(augmented.iter().take(n).map(|row| row[n]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(values: &[i128]) -> Vec<Rational> {
+        values.iter().copied().map(Rational::from).collect()
+    }
+
+    #[test]
+    fn test_row_reduce() {
+        let mut m = matrix([[1, 2, 3], [2, 4, 6], [1, 0, 1]]);
+        assert_eq!(row_reduce(&mut m), 2);
+        assert_eq!(m, matrix([[1, 0, 1], [0, 1, 1], [0, 0, 0]]));
+        assert_eq!(row_reduce::<i64>(&mut []), 0);
+    }
+
+    #[test]
+    fn test_solve() {
+        let a = matrix([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]]);
+        assert_eq!(solve(&a, &vector(&[8, -11, -3])), Some(vector(&[2, 3, -1])));
+
+        let a = matrix([[1, 1], [1, -1]]);
+        let x = solve(&a, &vector(&[1, 0])).unwrap();
+        assert_eq!(x, [Rational::new(1, 2), Rational::new(1, 2)]);
+
+        // overdetermined but consistent
+        let a = matrix([[1], [2], [3]]);
+        assert_eq!(solve(&a, &vector(&[2, 4, 6])), Some(vector(&[2])));
+        assert_eq!(solve(&a, &vector(&[2, 4, 7])), None);
+
+        // infinitely many solutions
+        let a = matrix([[1, 1], [2, 2]]);
+        assert_eq!(solve(&a, &vector(&[1, 2])), None);
+
+        // eliminating the first column overflows
+        let a = matrix([[i128::MAX, 1], [i128::MAX - 1, 3]]);
+        assert_eq!(solve(&a, &vector(&[0, 1])), None);
+    }
+}
@@ -0,0 +1,117 @@
+use super::{gcd, rational::Rational};
+
+/// Leading entries of the difference table `[y0, Δy0, Δ²y0, ...]`, without trailing zeros.
+/// `None` if a difference overflows.
+pub fn forward_differences(samples: &[i128]) -> Option<Vec<i128>> {
+    let mut leading = Vec::with_capacity(samples.len());
+    let mut row = samples.to_vec();
+
+    while row.iter().any(|&x| x != 0) {
+        leading.push(row[0]);
+        row = row
+            .windows(2)
+            .map(|x| x[1].checked_sub(x[0]))
+            .collect::<Option<_>>()?;
+    }
+
+    Some(leading)
+}
+
+/// Evaluates Newton's forward difference formula `Σ Δᵏy0 * C(x, k)` at any integer `x`.
+pub fn newton(leading: &[i128], x: i128) -> Option<i128> {
+    let mut total: i128 = 0;
+    // generalized binomial coefficient C(x, k), exact at every step
+    let mut binomial: i128 = 1;
+
+    for (k, d) in leading.iter().enumerate() {
+        if k > 0 {
+            binomial = binomial.checked_mul(x.checked_sub(k as i128 - 1)?)? / k as i128;
+        }
+        total = total.checked_add(d.checked_mul(binomial)?)?;
+    }
+
+    Some(total)
+}
+
+/// Value at `x` of the lowest degree polynomial through `samples` taken at `0, 1, 2, ...`.
+pub fn extrapolate(samples: &[i128], x: i128) -> Option<i128> {
+    newton(&forward_differences(samples)?, x)
+}
+
+/// Lagrange interpolation through `points` evaluated at `x`.
+/// `None` on overflow or if two points share an `x`.
+pub fn lagrange(points: &[(i128, i128)], x: i128) -> Option<Rational> {
+    let mut total: (i128, i128) = (0, 1);
+
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let mut term: (i128, i128) = (yi, 1);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                term = reduce(
+                    term.0.checked_mul(x.checked_sub(xj)?)?,
+                    term.1.checked_mul(xi.checked_sub(xj)?)?,
+                )?;
+            }
+        }
+
+        let (a, b) = total;
+        let (c, d) = term;
+        let g = gcd(b, d);
+        total = reduce(
+            a.checked_mul(d / g)?.checked_add(c.checked_mul(b / g)?)?,
+            b.checked_mul(d / g)?,
+        )?;
+    }
+
+    Some(Rational::new(total.0, total.1))
+}
+
+fn reduce(numerator: i128, denominator: i128) -> Option<(i128, i128)> {
+    if denominator == 0 {
+        return None;
+    }
+    let g = gcd(numerator, denominator) * denominator.signum();
+    Some((numerator / g, denominator / g))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_differences() {
+        assert_eq!(
+            forward_differences(&[1, 3, 6, 10, 15, 21]),
+            Some(vec![1, 2, 1])
+        );
+        assert_eq!(forward_differences(&[0, 0]), Some(vec![]));
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], 6), Some(68));
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), Some(5));
+        assert_eq!(extrapolate(&[0, 0, 0], 100), Some(0));
+    }
+
+    #[test]
+    fn test_huge_index() {
+        // x² + x + 1
+        let samples = [1, 3, 7];
+        let x = 1_000_000_000_000i128;
+        assert_eq!(extrapolate(&samples, x), Some(x * x + x + 1));
+        assert_eq!(extrapolate(&samples, -x), Some(x * x - x + 1));
+        assert_eq!(extrapolate(&samples, i128::MAX), None);
+        assert_eq!(extrapolate(&samples, i128::MIN), None);
+        // constant and linear sequences reach the ends of the range
+        assert_eq!(extrapolate(&[5, 5], i128::MIN), Some(5));
+        assert_eq!(extrapolate(&[0, 1], i128::MAX), Some(i128::MAX));
+        assert_eq!(extrapolate(&[0, -1], i128::MAX), Some(-i128::MAX));
+    }
+
+    #[test]
+    fn test_lagrange() {
+        let points = [(1, 3), (3, 13), (-2, 3)];
+        assert_eq!(lagrange(&points, 10), Some(Rational::from(111)));
+        assert_eq!(lagrange(&[(0, 0), (2, 1)], 1), Some(Rational::new(1, 2)));
+        assert_eq!(lagrange(&[(0, 0), (2, 1)], -1), Some(Rational::new(-1, 2)));
+        assert_eq!(lagrange(&[(1, 1), (1, 2)], 0), None);
+        assert_eq!(lagrange(&[(0, 0), (1, 1)], i128::MIN), None);
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use super::{checked_gcd, Integer};
+
+/// Exact fraction, always reduced with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T = i128> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: Integer + Neg<Output = T>> Rational<T> {
+    /// Panics if `denom` is zero or the reduced fraction doesn't fit.
+    pub fn new(numer: T, denom: T) -> Self {
+        assert!(denom != T::ZERO, "zero denominator");
+        Self::checked_new(numer, denom).expect("rational overflow")
+    }
+
+    /// `None` if `denom` is zero or the reduced fraction doesn't fit, like `1 / MIN`.
+    pub fn checked_new(numer: T, denom: T) -> Option<Self> {
+        if denom == T::ZERO {
+            return None;
+        }
+        let g = checked_gcd(numer, denom)?;
+        let (numer, denom) = (numer / g, denom / g);
+        if denom < T::ZERO {
+            Some(Self {
+                numer: numer.checked_neg()?,
+                denom: denom.checked_neg()?,
+            })
+        } else {
+            Some(Self { numer, denom })
+        }
+    }
+
+    pub fn numer(&self) -> T {
+        self.numer
+    }
+
+    pub fn denom(&self) -> T {
+        self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == T::ONE
+    }
+
+    pub fn to_integer(&self) -> Option<T> {
+        self.is_integer().then_some(self.numer)
+    }
+
+    pub fn floor(&self) -> T {
+        let q = self.numer / self.denom;
+        if self.numer % self.denom < T::ZERO {
+            q - T::ONE
+        } else {
+            q
+        }
+    }
+
+    pub fn ceil(&self) -> T {
+        -(-*self).floor()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numer: self.numer.abs(),
+            denom: self.denom,
+        }
+    }
+
+    /// Panics on zero.
+    pub fn recip(&self) -> Self {
+        Self::new(self.denom, self.numer)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            numer: self.numer.checked_neg()?,
+            denom: self.denom,
+        })
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        // dividing by the common factor first keeps the intermediate products small
+        let g = checked_gcd(self.denom, rhs.denom)?;
+        let numer = self
+            .numer
+            .checked_mul(rhs.denom / g)?
+            .checked_add(rhs.numer.checked_mul(self.denom / g)?)?;
+        Self::checked_new(numer, (self.denom / g).checked_mul(rhs.denom)?)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // cross cancelling first, the products of the reduced parts are already in lowest terms
+        let a = Self::checked_new(self.numer, rhs.denom)?;
+        let b = Self::checked_new(rhs.numer, self.denom)?;
+        Some(Self {
+            numer: a.numer.checked_mul(b.numer)?,
+            denom: a.denom.checked_mul(b.denom)?,
+        })
+    }
+
+    /// `None` on overflow and division by zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(Self::checked_new(rhs.denom, rhs.numer)?)
+    }
+
+    pub fn checked_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(
+            self.numer
+                .checked_mul(other.denom)?
+                .cmp(&other.numer.checked_mul(self.denom)?),
+        )
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> From<T> for Rational<T> {
+    fn from(value: T) -> Self {
+        Self {
+            numer: value,
+            denom: T::ONE,
+        }
+    }
+}
+
+// the operators panic on overflow instead of wrapping in release builds, use the checked
+// methods where that can happen
+impl<T: Integer + Neg<Output = T>> Add for Rational<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("rational overflow")
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> Sub for Rational<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect("rational overflow")
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> Mul for Rational<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect("rational overflow")
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> Div for Rational<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        self.mul(rhs.recip())
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> Neg for Rational<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("rational overflow")
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.checked_cmp(other).expect("rational overflow")
+    }
+}
+
+impl<T: Integer + Display> Display for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom == T::ONE {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let half = Rational::new(1, 2);
+        let third = Rational::new(-2, -6);
+        assert_eq!(third, Rational::new(1, 3));
+        assert_eq!(half + third, Rational::new(5, 6));
+        assert_eq!(half - third, Rational::new(1, 6));
+        assert_eq!(half * third, Rational::new(1, 6));
+        assert_eq!(half / third, Rational::new(3, 2));
+        assert_eq!(Rational::new(3, -6), -half);
+        assert_eq!((half + half).to_integer(), Some(1));
+        assert_eq!(half.to_string(), "1/2");
+        assert_eq!(Rational::from(4).to_string(), "4");
+    }
+
+    #[test]
+    fn test_order() {
+        let a = Rational::new(-7, 2);
+        assert_eq!((a.floor(), a.ceil()), (-4, -3));
+        assert_eq!((a.abs().floor(), a.abs().ceil()), (3, 4));
+        assert!(a < Rational::from(-3) && Rational::from(-3) < Rational::new(-1, 3));
+
+        // exact where f64 can no longer tell the values apart
+        let big = 200_000_000_000_000_000i128;
+        assert!(Rational::new(big + 1, 3) > Rational::new(big, 3));
+        assert_eq!((big + 1) as f64, big as f64);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let big = Rational::from(i128::MAX);
+        let tiny = Rational::new(1, i128::MAX);
+        assert_eq!(big.checked_add(Rational::from(1)), None);
+        assert_eq!(big.checked_mul(Rational::from(2)), None);
+        assert_eq!(big.checked_mul(tiny), Some(Rational::from(1)));
+        assert_eq!(Rational::from(i128::MIN).checked_neg(), None);
+        assert_eq!(Rational::checked_new(1, i128::MIN), None);
+        assert_eq!(Rational::from(1).checked_div(Rational::from(0)), None);
+        assert_eq!(big.checked_cmp(&Rational::new(i128::MAX, 2)), None);
+        assert_eq!(
+            Rational::new(1, 3).checked_cmp(&Rational::new(1, 2)),
+            Some(Ordering::Less)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "rational overflow")]
+    fn test_overflow_panics() {
+        let _ = Rational::from(i128::MAX) + Rational::from(1);
+    }
+}
@@ -1,20 +1,195 @@
-use std::str::{pattern::Pattern, FromStr};
+pub mod combinator;
+pub mod record;
 
-pub fn to_vec<'a, T, P>(s: &'a str, pat: P) -> Vec<T>
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Anything `str::split` accepts, without depending on the unstable `Pattern` trait.
+pub trait Separator {
+    fn split<'a>(&self, s: &'a str) -> impl Iterator<Item = &'a str>;
+}
+
+impl Separator for char {
+    fn split<'a>(&self, s: &'a str) -> impl Iterator<Item = &'a str> {
+        s.split(*self)
+    }
+}
+
+impl Separator for &str {
+    fn split<'a>(&self, s: &'a str) -> impl Iterator<Item = &'a str> {
+        s.split(*self)
+    }
+}
+
+impl<const N: usize> Separator for [char; N] {
+    fn split<'a>(&self, s: &'a str) -> impl Iterator<Item = &'a str> {
+        s.split(*self)
+    }
+}
+
+impl<F: Fn(char) -> bool> Separator for F {
+    fn split<'a>(&self, s: &'a str) -> impl Iterator<Item = &'a str> {
+        s.split(self)
+    }
+}
+
+pub fn to_vec<T, P>(s: &str, pat: P) -> Vec<T>
 where
     T: FromStr,
-    P: Pattern<'a>,
+    P: Separator,
 {
-    s.split(pat).filter_map(|x| x.parse().ok()).collect()
+    pat.split(s).filter_map(|x| x.parse().ok()).collect()
 }
 
-pub fn to_vec_map<'a, T, U, P>(s: &'a str, pat: P, func: impl FnMut(T) -> U) -> Vec<U>
+pub fn to_vec_map<T, U, P>(s: &str, pat: P, func: impl FnMut(T) -> U) -> Vec<U>
 where
     T: FromStr,
-    P: Pattern<'a>,
+    P: Separator,
 {
-    s.split(pat)
+    pat.split(s)
         .filter_map(|x| x.parse().ok())
         .map(func)
         .collect()
 }
+
+/// What the strict parsers do with empty fragments, like the ones between repeated separators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Empty {
+    #[default]
+    Error,
+    Skip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub token: String,
+    /// Position of the token among all fragments, empty ones included.
+    pub index: usize,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "could not parse {:?} at index {}",
+            self.token, self.index
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn try_to_vec<T, P>(s: &str, pat: P, empty: Empty) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    P: Separator,
+{
+    try_to_vec_map(s, pat, empty, |x| x)
+}
+
+pub fn try_to_vec_map<T, U, P>(
+    s: &str,
+    pat: P,
+    empty: Empty,
+    mut func: impl FnMut(T) -> U,
+) -> Result<Vec<U>, ParseError>
+where
+    T: FromStr,
+    P: Separator,
+{
+    pat.split(s)
+        .enumerate()
+        .filter(|(_, x)| empty == Empty::Error || !x.is_empty())
+        .map(|(index, x)| {
+            x.parse().map(&mut func).map_err(|_| ParseError {
+                token: x.to_string(),
+                index,
+            })
+        })
+        .collect()
+}
+
+/// Error of the line that didn't parse in `try_lines`, `line` is one based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError<E> {
+    pub line: usize,
+    pub error: E,
+}
+
+impl<E: Display> Display for LineError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl<E: Display + fmt::Debug> std::error::Error for LineError<E> {}
+
+/// Parses every line, keeping the error of the first one that fails.
+pub fn try_lines<T: FromStr>(s: &str) -> Result<Vec<T>, LineError<T::Err>> {
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|error| LineError { line: i + 1, error })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lenient() {
+        assert_eq!(to_vec::<u32, _>(" 4  12 x 7", ' '), [4, 12, 7]);
+        assert_eq!(to_vec_map(" 4  12", ' ', |x: u32| x * 2), [8, 24]);
+    }
+
+    #[test]
+    fn test_separators() {
+        assert_eq!(to_vec::<u32, _>("1, 2, 3", ", "), [1, 2, 3]);
+        assert_eq!(to_vec::<u32, _>("1-2=3", ['-', '=']), [1, 2, 3]);
+        assert_eq!(to_vec::<u32, _>("1a2bb3", char::is_alphabetic), [1, 2, 3]);
+        let separator = String::from("::");
+        assert_eq!(to_vec::<u32, _>("1::2", separator.as_str()), [1, 2]);
+    }
+
+    #[test]
+    fn test_strict() {
+        assert_eq!(
+            try_to_vec(" 4  12 7", ' ', Empty::Skip),
+            Ok(vec![4u32, 12, 7])
+        );
+        assert_eq!(
+            try_to_vec::<u32, _>(" 4  12 4x", ' ', Empty::Skip),
+            Err(ParseError {
+                token: "4x".to_string(),
+                index: 4
+            })
+        );
+        assert_eq!(
+            try_to_vec::<u32, _>("4  12", ' ', Empty::Error),
+            Err(ParseError {
+                token: String::new(),
+                index: 1
+            })
+        );
+        assert_eq!(
+            try_to_vec_map("1,2", ',', Empty::Error, |x: i32| -x),
+            Ok(vec![-1, -2])
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(try_lines::<u8>("1\n2\n"), Ok(vec![1, 2]));
+        let error = try_lines::<u8>("1\n2\n300").unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(
+            error.to_string(),
+            "line 3: number too large to fit in target type"
+        );
+    }
+}
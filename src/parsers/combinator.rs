@@ -0,0 +1,361 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Remaining input, still knowing the whole source so errors can point at a line and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Input<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self { source, offset: 0 }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// One based line and column of the current position, the column counts chars.
+    pub fn position(&self) -> (usize, usize) {
+        let before = &self.source[..self.offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        (line, column)
+    }
+
+    pub fn error(&self, expected: impl Into<String>) -> Error {
+        let (line, column) = self.position();
+        Error {
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    fn advance(&self, n: usize) -> (&'a str, Self) {
+        let taken = &self.rest()[..n];
+        (
+            taken,
+            Self {
+                source: self.source,
+                offset: self.offset + n,
+            },
+        )
+    }
+
+    fn take_while(&self, f: impl Fn(char) -> bool) -> (&'a str, Self) {
+        let n = self.rest().find(|c| !f(c)).unwrap_or(self.rest().len());
+        self.advance(n)
+    }
+}
+
+pub type PResult<'a, T> = Result<(T, Input<'a>), Error>;
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: Input<'a>) -> PResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(Input<'a>) -> PResult<'a, T>,
+{
+    fn parse(&self, input: Input<'a>) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Runs `parser` on the whole of `s`, only trailing whitespace may be left over.
+pub fn parse_all<'a, T>(s: &'a str, parser: impl Parser<'a, T>) -> Result<T, Error> {
+    let (value, rest) = parser.parse(Input::new(s))?;
+    let (_, rest) = rest.take_while(char::is_whitespace);
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(rest.error("end of input"))
+    }
+}
+
+pub fn tag<'a, 'p>(expected: &'p str) -> impl Fn(Input<'a>) -> PResult<'a, &'a str> + 'p {
+    move |input| {
+        if input.rest().starts_with(expected) {
+            Ok(input.advance(expected.len()))
+        } else {
+            Err(input.error(format!("{expected:?}")))
+        }
+    }
+}
+
+/// First of `options` that matches.
+pub fn one_of<'a, 'p>(options: &'p [&'p str]) -> impl Fn(Input<'a>) -> PResult<'a, &'a str> + 'p {
+    move |input| {
+        options
+            .iter()
+            .find(|x| input.rest().starts_with(**x))
+            .map(|x| input.advance(x.len()))
+            .ok_or_else(|| input.error(format!("one of {options:?}")))
+    }
+}
+
+fn number<'a, T: FromStr>(input: Input<'a>, signed: bool) -> PResult<'a, T> {
+    let sign = usize::from(signed && input.rest().starts_with(['-', '+']));
+    let digits = input.rest()[sign..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.rest().len() - sign);
+
+    let expected = if signed { "signed number" } else { "number" };
+    if digits == 0 {
+        return Err(input.error(expected));
+    }
+
+    let (s, rest) = input.advance(sign + digits);
+    let value = s
+        .parse()
+        .map_err(|_| input.error(format!("{expected} in range")))?;
+    Ok((value, rest))
+}
+
+pub fn unsigned<'a, T: FromStr>(input: Input<'a>) -> PResult<'a, T> {
+    number(input, false)
+}
+
+pub fn signed<'a, T: FromStr>(input: Input<'a>) -> PResult<'a, T> {
+    number(input, true)
+}
+
+/// Non empty run of alphanumeric characters.
+pub fn word<'a>(input: Input<'a>) -> PResult<'a, &'a str> {
+    match input.take_while(char::is_alphanumeric) {
+        ("", _) => Err(input.error("word")),
+        x => Ok(x),
+    }
+}
+
+/// Any number of spaces or tabs, not newlines.
+pub fn spaces<'a>(input: Input<'a>) -> PResult<'a, &'a str> {
+    Ok(input.take_while(|c| c == ' ' || c == '\t'))
+}
+
+pub fn map<'a, T, U>(
+    parser: impl Parser<'a, T>,
+    f: impl Fn(T) -> U,
+) -> impl Fn(Input<'a>) -> PResult<'a, U> {
+    move |input| parser.parse(input).map(|(x, rest)| (f(x), rest))
+}
+
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Fn(Input<'a>) -> PResult<'a, Option<T>> {
+    move |input| match parser.parse(input) {
+        Ok((x, rest)) => Ok((Some(x), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+pub fn pair<'a, T, U>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, U>,
+) -> impl Fn(Input<'a>) -> PResult<'a, (T, U)> {
+    move |input| {
+        let (a, input) = first.parse(input)?;
+        let (b, input) = second.parse(input)?;
+        Ok(((a, b), input))
+    }
+}
+
+pub fn preceded<'a, T, U>(
+    prefix: impl Parser<'a, T>,
+    parser: impl Parser<'a, U>,
+) -> impl Fn(Input<'a>) -> PResult<'a, U> {
+    move |input| {
+        let (_, input) = prefix.parse(input)?;
+        parser.parse(input)
+    }
+}
+
+pub fn terminated<'a, T, U>(
+    parser: impl Parser<'a, T>,
+    suffix: impl Parser<'a, U>,
+) -> impl Fn(Input<'a>) -> PResult<'a, T> {
+    move |input| {
+        let (x, input) = parser.parse(input)?;
+        let (_, input) = suffix.parse(input)?;
+        Ok((x, input))
+    }
+}
+
+/// `key`, then `separator`, then `value`.
+pub fn key_value<'a, K, S, V>(
+    key: impl Parser<'a, K>,
+    separator: impl Parser<'a, S>,
+    value: impl Parser<'a, V>,
+) -> impl Fn(Input<'a>) -> PResult<'a, (K, V)> {
+    move |input| {
+        let (k, input) = key.parse(input)?;
+        let (_, input) = separator.parse(input)?;
+        let (v, input) = value.parse(input)?;
+        Ok(((k, v), input))
+    }
+}
+
+/// One or more items with `separator` in between.
+/// An item has to follow every separator, unless only whitespace is left after it.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Fn(Input<'a>) -> PResult<'a, Vec<T>> {
+    move |input| {
+        let (first, mut input) = item.parse(input)?;
+        let mut items = vec![first];
+
+        while let Ok((_, after)) = separator.parse(input) {
+            if after == input || after.rest().trim().is_empty() {
+                break;
+            }
+            let (x, rest) = item.parse(after)?;
+            items.push(x);
+            input = rest;
+        }
+
+        Ok((items, input))
+    }
+}
+
+/// Signed numbers separated by spaces, leading, trailing and repeated spaces are fine.
+pub fn numbers<'a, T: FromStr>(input: Input<'a>) -> PResult<'a, Vec<T>> {
+    let mut items = Vec::new();
+    let (_, mut input) = spaces(input)?;
+
+    while input
+        .rest()
+        .starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+')
+    {
+        let (x, rest) = signed(input)?;
+        items.push(x);
+        (_, input) = spaces(rest)?;
+    }
+
+    if items.is_empty() {
+        return Err(input.error("numbers"));
+    }
+    Ok((items, input))
+}
+
+/// `label:` followed by optional spaces and `body`.
+pub fn section<'a, 'p, T>(
+    label: &'p str,
+    body: impl Parser<'a, T> + 'p,
+) -> impl Fn(Input<'a>) -> PResult<'a, T> + 'p {
+    move |input| {
+        let (_, input) = tag(label).parse(input)?;
+        let (_, input) = tag(":").parse(input)?;
+        let (_, input) = spaces(input)?;
+        body.parse(input)
+    }
+}
+
+// a newline that doesn't start a blank line, so lines stop at the end of a block
+fn line_break<'a>(input: Input<'a>) -> PResult<'a, &'a str> {
+    match input.rest().strip_prefix('\n') {
+        Some(rest) if !rest.starts_with('\n') => Ok(input.advance(1)),
+        _ => Err(input.error("line break")),
+    }
+}
+
+/// One item per line.
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Fn(Input<'a>) -> PResult<'a, Vec<T>> {
+    separated(item, line_break)
+}
+
+/// Items separated by blank lines.
+pub fn blocks<'a, T>(item: impl Parser<'a, T>) -> impl Fn(Input<'a>) -> PResult<'a, Vec<T>> {
+    separated(item, tag("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(parse_all("42", unsigned::<u32>), Ok(42));
+        assert_eq!(parse_all("-42", signed::<i32>), Ok(-42));
+        assert_eq!(parse_all("  1 -2   3 ", numbers::<i64>), Ok(vec![1, -2, 3]));
+        assert_eq!(parse_all("1\n2", lines(unsigned::<u8>)), Ok(vec![1, 2]));
+        assert_eq!(parse_all("1\n2\n", lines(unsigned::<u8>)), Ok(vec![1, 2]));
+        assert_eq!(
+            parse_all("300", unsigned::<u8>),
+            Err(Error {
+                line: 1,
+                column: 1,
+                expected: "number in range".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+        let parser = pair(
+            terminated(section("Time", numbers::<u64>), tag("\n")),
+            section("Distance", numbers::<u64>),
+        );
+        assert_eq!(
+            parse_all(input, parser),
+            Ok((vec![7, 15, 30], vec![9, 40, 200]))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let game = preceded(
+            tag("Game "),
+            key_value(
+                unsigned::<u32>,
+                tag(": "),
+                separated(
+                    pair(terminated(unsigned::<u32>, tag(" ")), word),
+                    one_of(&[", ", "; "]),
+                ),
+            ),
+        );
+        let input = "Game 1: 3 blue, 4 red; 1 red\nGame 2: 1 blue, x green";
+
+        let (first, _) = game.parse(Input::new(input)).unwrap();
+        assert_eq!(first, (1, vec![(3, "blue"), (4, "red"), (1, "red")]));
+
+        let error = parse_all(input, lines(&game)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 17));
+        assert_eq!(error.to_string(), "line 2, column 17: expected number");
+
+        let blocks_input = "a\nb\n\nc";
+        assert_eq!(
+            parse_all(blocks_input, blocks(lines(word))),
+            Ok(vec![vec!["a", "b"], vec!["c"]])
+        );
+    }
+}
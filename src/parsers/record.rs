@@ -0,0 +1,448 @@
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Literal text of the format wasn't found, `column` is one based and counts chars.
+    Literal { expected: String, column: usize },
+    /// The text captured for a field didn't parse.
+    Field { name: &'static str, value: String },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Literal { expected, column } => {
+                write!(f, "column {column}: expected {expected}")
+            }
+            Error::Field { name, value } => write!(f, "invalid {name}: {value:?}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Mistakes in a format string, `record!` reports them at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    Unclosed,
+    Unopened,
+    Adjacent,
+    UnknownField,
+    MissingField,
+    DuplicateField,
+}
+
+impl FormatError {
+    pub const fn message(self) -> &'static str {
+        match self {
+            FormatError::Unclosed => "record format has an unclosed `{`, write `{{` for a brace",
+            FormatError::Unopened => "record format has an unopened `}`, write `}}` for a brace",
+            FormatError::Adjacent => "record format needs literal text between placeholders",
+            FormatError::UnknownField => "record format has a placeholder for no field",
+            FormatError::MissingField => "record format has no placeholder for a field",
+            FormatError::DuplicateField => "record format has two placeholders for a field",
+        }
+    }
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    /// byte span of plain text, `{{` and `}}` are one byte spans of their brace
+    Literal(usize, usize),
+    /// byte span of a placeholder name
+    Field(usize, usize),
+}
+
+// the token at `at` and where the next one starts, const so `record!` can check at compile time
+const fn token(format: &[u8], at: usize) -> Result<(Token, usize), FormatError> {
+    let escaped = at + 1 < format.len() && format[at + 1] == format[at];
+    match format[at] {
+        b'{' | b'}' if escaped => Ok((Token::Literal(at, at + 1), at + 2)),
+        b'}' => Err(FormatError::Unopened),
+        b'{' => {
+            let mut end = at + 1;
+            while end < format.len() && format[end] != b'}' {
+                if format[end] == b'{' {
+                    return Err(FormatError::Unclosed);
+                }
+                end += 1;
+            }
+            if end == format.len() {
+                return Err(FormatError::Unclosed);
+            }
+            Ok((Token::Field(at + 1, end), end + 1))
+        }
+        _ => {
+            let mut end = at;
+            while end < format.len() && format[end] != b'{' && format[end] != b'}' {
+                end += 1;
+            }
+            Ok((Token::Literal(at, end), end))
+        }
+    }
+}
+
+const fn span_is(format: &[u8], (start, end): (usize, usize), name: &[u8]) -> bool {
+    if end - start != name.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < name.len() {
+        if format[start + i] != name[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Checks the braces of `format` and that it has one placeholder for each of `fields`,
+/// besides any number of `{_}`.
+pub const fn check(format: &str, fields: &[&str]) -> Result<(), FormatError> {
+    let format = format.as_bytes();
+    let mut counts = [0u8; 64];
+    assert!(fields.len() <= counts.len(), "too many record fields");
+
+    let mut at = 0;
+    let mut after_field = false;
+    while at < format.len() {
+        let (token, next) = match token(format, at) {
+            Ok(x) => x,
+            Err(e) => return Err(e),
+        };
+        at = next;
+
+        let Token::Field(start, end) = token else {
+            after_field = false;
+            continue;
+        };
+        if after_field {
+            return Err(FormatError::Adjacent);
+        }
+        after_field = true;
+        if span_is(format, (start, end), b"_") {
+            continue;
+        }
+
+        let mut i = 0;
+        while i < fields.len() && !span_is(format, (start, end), fields[i].as_bytes()) {
+            i += 1;
+        }
+        if i == fields.len() {
+            return Err(FormatError::UnknownField);
+        }
+        counts[i] += 1;
+    }
+
+    let mut i = 0;
+    while i < fields.len() {
+        match counts[i] {
+            0 => return Err(FormatError::MissingField),
+            1 => (),
+            _ => return Err(FormatError::DuplicateField),
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment<'f> {
+    Literal(String),
+    Field(&'f str),
+}
+
+/// A format string split into literal text and placeholders, `{name}` and `{_}` are
+/// placeholders and `{{` and `}}` stand for literal braces.
+#[derive(Debug)]
+pub struct Format<'f> {
+    segments: Vec<Segment<'f>>,
+}
+
+impl<'f> Format<'f> {
+    pub fn new(format: &'f str) -> Result<Self, FormatError> {
+        let bytes = format.as_bytes();
+        let mut segments = Vec::new();
+        let mut at = 0;
+
+        while at < bytes.len() {
+            let (token, next) = token(bytes, at)?;
+            at = next;
+            match (token, segments.last_mut()) {
+                (Token::Literal(start, end), Some(Segment::Literal(literal))) => {
+                    literal.push_str(&format[start..end]);
+                }
+                (Token::Literal(start, end), _) => {
+                    segments.push(Segment::Literal(format[start..end].to_string()));
+                }
+                (Token::Field(..), Some(Segment::Field(_))) => return Err(FormatError::Adjacent),
+                (Token::Field(start, end), _) => segments.push(Segment::Field(&format[start..end])),
+            }
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Matches `s` and returns the text of every named placeholder, trimmed.
+    /// A placeholder ends at the first occurrence of the literal text following it,
+    /// `{_}` matches like any other placeholder but isn't returned.
+    pub fn captures<'s>(&self, s: &'s str) -> Result<Captures<'f, 's>, Error> {
+        let column = |offset: usize| s[..offset].chars().count() + 1;
+        let mut fields = Vec::new();
+        let mut offset = 0;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Literal(literal) => {
+                    if !s[offset..].starts_with(literal.as_str()) {
+                        return Err(Error::Literal {
+                            expected: format!("{literal:?}"),
+                            column: column(offset),
+                        });
+                    }
+                    offset += literal.len();
+                }
+                Segment::Field(name) => {
+                    let end = match self.segments.get(i + 1) {
+                        Some(Segment::Literal(next)) => {
+                            offset
+                                + s[offset..].find(next.as_str()).ok_or(Error::Literal {
+                                    expected: format!("{next:?}"),
+                                    column: column(s.len()),
+                                })?
+                        }
+                        _ => s.len(),
+                    };
+                    if *name != "_" {
+                        fields.push((*name, s[offset..end].trim()));
+                    }
+                    offset = end;
+                }
+            }
+        }
+
+        if offset < s.len() {
+            return Err(Error::Literal {
+                expected: "end of line".to_string(),
+                column: column(offset),
+            });
+        }
+        Ok(Captures { fields })
+    }
+}
+
+#[derive(Debug)]
+pub struct Captures<'f, 's> {
+    fields: Vec<(&'f str, &'s str)>,
+}
+
+impl<'f, 's> Captures<'f, 's> {
+    pub fn get(&self, name: &str) -> Option<&'s str> {
+        self.fields.iter().find(|x| x.0 == name).map(|x| x.1)
+    }
+
+    /// Parses the field with `f`, the error of `f` is replaced by one naming the field.
+    /// Panics if the format has no placeholder `name`, which `record!` rules out.
+    pub fn parse<T, E>(
+        &self,
+        name: &'static str,
+        f: impl FnOnce(&'s str) -> Result<T, E>,
+    ) -> Result<T, Error> {
+        let value = self
+            .get(name)
+            .unwrap_or_else(|| panic!("no placeholder for {name} in the format"));
+        f(value).map_err(|_| Error::Field {
+            name,
+            value: value.to_string(),
+        })
+    }
+}
+
+/// Declares a struct together with a `FromStr` implementation matching a format string.
+/// Every field is parsed with `FromStr`, or with the function after `=>` which takes the
+/// captured `&str` and returns a `Result`. The format is checked against the fields at
+/// compile time and split into segments once, on the first line parsed.
+///
+/// ```
+/// use aoc::parsers::{try_to_vec, Empty};
+///
+/// aoc::record! {
+///     #[format("Card {_}: {winning} | {numbers}")]
+///     #[derive(Debug)]
+///     struct Card {
+///         winning: Vec<u32> => |s| try_to_vec(s, ' ', Empty::Skip),
+///         numbers: Vec<u32> => |s| try_to_vec(s, ' ', Empty::Skip),
+///     }
+/// }
+///
+/// let card: Card = "Card 1: 41 48 | 83  86 6".parse().unwrap();
+/// assert_eq!(card.numbers, [83, 86, 6]);
+/// ```
+///
+/// Every field needs exactly one placeholder:
+///
+/// ```compile_fail
+/// aoc::record! {
+///     #[format("{x},{why}")]
+///     struct Point {
+///         x: i32,
+///         y: i32,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! record {
+    (
+        #[format($format:literal)]
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field_vis:vis $field:ident : $ty:ty $(=> $parse:expr)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($(#[$field_meta])* $field_vis $field: $ty),*
+        }
+
+        const _: () = match $crate::parsers::record::check($format, &[$(stringify!($field)),*]) {
+            ::core::result::Result::Ok(()) => (),
+            ::core::result::Result::Err(e) => ::core::panic!("{}", e.message()),
+        };
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::parsers::record::Error;
+            fn from_str(s: &::core::primitive::str) -> ::core::result::Result<Self, Self::Err> {
+                static FORMAT: ::std::sync::OnceLock<$crate::parsers::record::Format> =
+                    ::std::sync::OnceLock::new();
+                let format = FORMAT.get_or_init(|| {
+                    $crate::parsers::record::Format::new($format).expect("checked at compile time")
+                });
+                let captures = format.captures(s)?;
+                ::core::result::Result::Ok(Self {
+                    $($field: $crate::record!(@field captures, $field $(, $parse)?)),*
+                })
+            }
+        }
+    };
+    (@field $captures:ident, $field:ident) => {
+        $captures.parse(stringify!($field), ::core::primitive::str::parse)?
+    };
+    (@field $captures:ident, $field:ident, $parse:expr) => {
+        $captures.parse(stringify!($field), $parse)?
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{try_to_vec, Empty};
+
+    crate::record! {
+        #[format("{{x={x},m={m},a={a},s={s}}}")]
+        #[derive(Debug, PartialEq)]
+        struct Xmas {
+            x: usize,
+            m: usize,
+            a: usize,
+            s: usize,
+        }
+    }
+
+    crate::record! {
+        #[format("{px}, {py} @ {velocity}")]
+        #[derive(Debug, PartialEq)]
+        struct Stone {
+            px: i64,
+            py: i64,
+            velocity: Vec<i64> => |s| try_to_vec(s, [',', ' '], Empty::Skip),
+        }
+    }
+
+    #[test]
+    fn test_captures() {
+        let format = Format::new("Game {id}: {_}").unwrap();
+        let game = format.captures("Game 12: 3 blue").unwrap();
+        assert_eq!(game.get("id"), Some("12"));
+        assert_eq!(game.get("_"), None);
+        assert_eq!(
+            Format::new("Game {id}:")
+                .unwrap()
+                .captures("Gam 1:")
+                .unwrap_err(),
+            Error::Literal {
+                expected: "\"Game \"".to_string(),
+                column: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_format_errors() {
+        assert_eq!(Format::new("{a").unwrap_err(), FormatError::Unclosed);
+        assert_eq!(Format::new("{a{b}").unwrap_err(), FormatError::Unclosed);
+        assert_eq!(Format::new("a}").unwrap_err(), FormatError::Unopened);
+        assert_eq!(Format::new("{a}{b}").unwrap_err(), FormatError::Adjacent);
+        assert!(Format::new("{{{a}}}").is_ok());
+
+        assert_eq!(check("{{x={x}}}", &["x"]), Ok(()));
+        assert_eq!(check("{_}: {x} {_}", &["x"]), Ok(()));
+        assert_eq!(
+            check("{x} {z}", &["x", "y"]),
+            Err(FormatError::UnknownField)
+        );
+        assert_eq!(check("{x}", &["x", "y"]), Err(FormatError::MissingField));
+        assert_eq!(check("{x} {x}", &["x"]), Err(FormatError::DuplicateField));
+        assert_eq!(check("{x}{y}", &["x", "y"]), Err(FormatError::Adjacent));
+    }
+
+    #[test]
+    fn test_record() {
+        assert_eq!(
+            "{x=787,m=2655,a=1222,s=2876}".parse(),
+            Ok(Xmas {
+                x: 787,
+                m: 2655,
+                a: 1222,
+                s: 2876
+            })
+        );
+        assert_eq!(
+            "19, 13 @ -2,  1, -2".parse(),
+            Ok(Stone {
+                px: 19,
+                py: 13,
+                velocity: vec![-2, 1, -2]
+            })
+        );
+
+        let error = "{x=787,m=2655,a=1222}".parse::<Xmas>().unwrap_err();
+        assert_eq!(error.to_string(), "column 22: expected \",s=\"");
+        let error = "{x=787,m=26a5,a=1222,s=2876}".parse::<Xmas>().unwrap_err();
+        assert_eq!(error.to_string(), "invalid m: \"26a5\"");
+        let error = "{x=1,m=2,a=3,s=4}!".parse::<Xmas>().unwrap_err();
+        assert_eq!(error.to_string(), "column 18: expected end of line");
+    }
+
+    #[test]
+    fn test_hygiene() {
+        // the generated code must not pick up a `str` from the caller's scope
+        #[allow(non_camel_case_types, dead_code)]
+        struct str;
+
+        crate::record! {
+            #[format("<{a}>")]
+            struct One {
+                a: u8,
+            }
+        }
+        assert_eq!("<7>".parse::<One>().map(|x| x.a), Ok(7));
+    }
+}
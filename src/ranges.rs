@@ -0,0 +1,416 @@
+use std::{
+    fmt::{self, Debug, Display},
+    iter,
+    ops::{Add, Div, Mul, Range, RangeInclusive, Sub},
+};
+
+pub trait Endpoint:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_endpoint {
+    ($($t:ty),*) => {
+        $(impl Endpoint for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_endpoint!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// An inclusive range ending at the largest value of its type, which has no half open form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeEndError;
+
+impl Display for RangeEndError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "inclusive range ends at the maximum of its type")
+    }
+}
+
+fn half_open<T: Endpoint>(range: RangeInclusive<T>) -> Result<Range<T>, RangeEndError> {
+    let (start, end) = range.into_inner();
+    Ok(start..end.checked_add(T::ONE).ok_or(RangeEndError)?)
+}
+
+/// Set of integers stored as sorted, disjoint and non-adjacent half open ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Endpoint> RangeSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    fn normalized(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|x| !x.is_empty());
+        ranges.sort_by_key(|x| x.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => merged.push(r),
+            }
+        }
+
+        Self { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |acc, r| acc + (r.end - r.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|x| x.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|x| x.end - T::ONE)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|x| x.end <= value);
+        self.ranges.get(i).is_some_and(|x| x.contains(&value))
+    }
+
+    pub fn insert(&mut self, range: impl Into<Self>) {
+        *self = self.union(&range.into());
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+
+        for a in self.ranges.iter() {
+            let mut start = a.start;
+            // skip ranges of other that end before this one
+            while other.ranges.get(j).is_some_and(|b| b.end <= start) {
+                j += 1;
+            }
+
+            let mut k = j;
+            while let Some(b) = other.ranges.get(k).filter(|b| b.start < a.end) {
+                if start < b.start {
+                    ranges.push(start..b.start);
+                }
+                start = start.max(b.end);
+                k += 1;
+            }
+
+            if start < a.end {
+                ranges.push(start..a.end);
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Values below `pivot` and values at or above it.
+    pub fn split_at(&self, pivot: T) -> (Self, Self) {
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+
+        for r in self.ranges.iter() {
+            if r.end <= pivot {
+                below.push(r.clone());
+            } else if r.start >= pivot {
+                above.push(r.clone());
+            } else {
+                below.push(r.start..pivot);
+                above.push(pivot..r.end);
+            }
+        }
+
+        (Self { ranges: below }, Self { ranges: above })
+    }
+
+    /// Moves every value by the offset between `from` and `to`, so unsigned sets can move down.
+    pub fn shift(&self, from: T, to: T) -> Self {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| {
+                if to >= from {
+                    r.start + (to - from)..r.end + (to - from)
+                } else {
+                    r.start - (from - to)..r.end - (from - to)
+                }
+            })
+            .collect();
+
+        Self { ranges }
+    }
+}
+
+impl<T: Endpoint> From<Range<T>> for RangeSet<T> {
+    fn from(value: Range<T>) -> Self {
+        Self::normalized(vec![value])
+    }
+}
+
+impl<T: Endpoint> TryFrom<RangeInclusive<T>> for RangeSet<T> {
+    type Error = RangeEndError;
+    fn try_from(value: RangeInclusive<T>) -> Result<Self, Self::Error> {
+        Ok(Self::normalized(vec![half_open(value)?]))
+    }
+}
+
+impl<T: Endpoint> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalized(iter.into_iter().collect())
+    }
+}
+
+/// Axis aligned box of integers, every axis is a half open range.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Boxes<const N: usize, T = usize> {
+    pub axes: [Range<T>; N],
+}
+
+impl<const N: usize, T: Endpoint> Boxes<N, T> {
+    pub fn new(axes: [Range<T>; N]) -> Self {
+        Self { axes }
+    }
+
+    pub fn from_inclusive(axes: [RangeInclusive<T>; N]) -> Result<Self, RangeEndError> {
+        let axes: Vec<_> = axes.into_iter().map(half_open).collect::<Result<_, _>>()?;
+        Ok(Self {
+            axes: axes.try_into().unwrap(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(|x| x.is_empty())
+    }
+
+    /// Length of the box along one axis.
+    pub fn size(&self, axis: usize) -> T {
+        let r = &self.axes[axis];
+        if r.is_empty() {
+            T::ZERO
+        } else {
+            r.end - r.start
+        }
+    }
+
+    pub fn volume(&self) -> T {
+        (0..N).fold(T::ONE, |acc, axis| acc * self.size(axis))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut axes = self.axes.clone();
+        for (a, b) in axes.iter_mut().zip(other.axes.iter()) {
+            *a = a.start.max(b.start)..a.end.min(b.end);
+        }
+        let result = Self { axes };
+        (!result.is_empty()).then_some(result)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.axes
+            .iter()
+            .zip(other.axes.iter())
+            .all(|(a, b)| a.start.max(b.start) < a.end.min(b.end))
+    }
+
+    /// Part below `pivot` and part at or above it along `axis`.
+    pub fn split_at(&self, axis: usize, pivot: T) -> (Option<Self>, Option<Self>) {
+        let r = &self.axes[axis];
+        let pivot = pivot.clamp(r.start, r.end.max(r.start));
+
+        let mut below = self.clone();
+        below.axes[axis].end = pivot;
+        let mut above = self.clone();
+        above.axes[axis].start = pivot;
+
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    /// Part where `predicate` holds and part where it doesn't along `axis`.
+    /// The predicate may change its value at most once along the axis, like a comparison.
+    pub fn split_by(
+        &self,
+        axis: usize,
+        predicate: impl Fn(T) -> bool,
+    ) -> (Option<Self>, Option<Self>) {
+        if self.is_empty() {
+            return (None, None);
+        }
+
+        let r = &self.axes[axis];
+        let first = predicate(r.start);
+        let (mut lo, mut hi) = (r.start, r.end);
+        while lo < hi {
+            let mid = lo + (hi - lo) / (T::ONE + T::ONE);
+            if predicate(mid) == first {
+                lo = mid + T::ONE;
+            } else {
+                hi = mid;
+            }
+        }
+
+        let (head, tail) = self.split_at(axis, lo);
+        if first {
+            (head, tail)
+        } else {
+            (tail, head)
+        }
+    }
+
+    /// Every point in the box, last axis changing fastest.
+    pub fn points(&self) -> impl Iterator<Item = [T; N]> + '_ {
+        let mut next = (!self.is_empty()).then(|| self.axes.each_ref().map(|x| x.start));
+
+        iter::from_fn(move || {
+            let current = next?;
+            let mut point = current;
+            next = None;
+
+            for axis in (0..N).rev() {
+                point[axis] = point[axis] + T::ONE;
+                if point[axis] < self.axes[axis].end {
+                    next = Some(point);
+                    break;
+                }
+                point[axis] = self.axes[axis].start;
+            }
+
+            Some(current)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let set: RangeSet<i32> = [5..8, 1..3, 3..4, 10..10, 6..12].into_iter().collect();
+        assert_eq!(set.ranges(), [1..4, 5..12]);
+        assert_eq!(set.len(), 10);
+        assert_eq!((set.min(), set.max()), (Some(1), Some(11)));
+        assert!(set.contains(3) && set.contains(5) && !set.contains(4) && !set.contains(12));
+        assert_eq!(RangeSet::try_from(1..=4), Ok(RangeSet::from(1..5)));
+        assert_eq!(
+            RangeSet::try_from(0..=u8::MAX - 1),
+            Ok(RangeSet::from(0..u8::MAX))
+        );
+        assert_eq!(RangeSet::try_from(0..=u8::MAX), Err(RangeEndError));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: RangeSet<u64> = [0..10, 20..30].into_iter().collect();
+        let b: RangeSet<u64> = [5..25, 28..29].into_iter().collect();
+        assert_eq!(a.union(&b), RangeSet::from(0..30));
+        assert_eq!(a.intersection(&b).ranges(), [5..10, 20..25, 28..29]);
+        assert_eq!(a.difference(&b).ranges(), [0..5, 25..28, 29..30]);
+        assert_eq!(b.difference(&a), RangeSet::from(10..20));
+
+        // other strictly containing self
+        let inner = RangeSet::from(3..4);
+        assert_eq!(inner.intersection(&a), RangeSet::from(3..4));
+        assert!(inner.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_split_shift() {
+        let a: RangeSet<u64> = [0..10, 20..30].into_iter().collect();
+        let (below, above) = a.split_at(25);
+        assert_eq!(below.ranges(), [0..10, 20..25]);
+        assert_eq!(above, RangeSet::from(25..30));
+        assert_eq!(above.shift(25, 5), RangeSet::from(5..10));
+        assert_eq!(above.shift(5, 25), RangeSet::from(45..50));
+    }
+
+    #[test]
+    fn test_boxes() {
+        let a = Boxes::new([0..4, 0..3]);
+        let b = Boxes::from_inclusive([2..=5, 1..=1]).unwrap();
+        assert_eq!(
+            Boxes::from_inclusive([0..=1, 0..=u8::MAX]),
+            Err(RangeEndError)
+        );
+        assert_eq!(a.volume(), 12);
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersection(&b), Some(Boxes::new([2..4, 1..2])));
+        assert!(!a.overlaps(&Boxes::new([4..6, 0..3])));
+        assert_eq!(a.intersection(&Boxes::new([4..6, 0..3])), None);
+        assert_eq!(
+            b.points().collect::<Vec<_>>(),
+            [[2, 1], [3, 1], [4, 1], [5, 1]]
+        );
+    }
+
+    #[test]
+    fn test_boxes_split() {
+        let a: Boxes<3> = Boxes::from_inclusive([1..=10, 1..=10, 1..=10]).unwrap();
+        let (lower, upper) = a.split_by(1, |x| x < 4);
+        assert_eq!(lower.map(|x| x.volume()), Some(300));
+        assert_eq!(upper.map(|x| x.volume()), Some(700));
+
+        let (matching, rest) = a.split_by(0, |x| x > 7);
+        assert_eq!(matching, Some(Boxes::new([8..11, 1..11, 1..11])));
+        assert_eq!(rest, Some(Boxes::new([1..8, 1..11, 1..11])));
+
+        assert_eq!(a.split_by(2, |x| x < 100), (Some(a.clone()), None));
+        assert_eq!(a.split_at(2, 0), (None, Some(a)));
+    }
+}
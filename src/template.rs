@@ -36,6 +36,46 @@ pub fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &st
     }
 }
 
+/// Whether the solution was run with `--<flag>`, like `cargo solve 20 -- --dot`.
+pub fn flag_requested(flag: &str) -> bool {
+    env::args().skip(1).any(|x| x == format!("--{flag}"))
+}
+
+/// The argument after `--<flag>`, like `3` in `cargo solve 20 -- --trace --presses 3`.
+pub fn flag_value(flag: &str) -> Option<String> {
+    let flag = format!("--{flag}");
+    env::args().skip_while(|x| *x != flag).nth(1)
+}
+
+/// What a `--<flag>` handler of `solution!` returns, finished once the handler is done.
+pub trait FlagOutput {
+    fn finish(self, day: u8);
+}
+
+impl FlagOutput for () {
+    fn finish(self, _day: u8) {}
+}
+
+/// Graphviz source of a day, written to `target/<day>.dot`.
+pub struct Dot(pub String);
+
+impl FlagOutput for Dot {
+    fn finish(self, day: u8) {
+        let folder = env::current_dir().unwrap().join("target");
+        fs::create_dir_all(&folder).expect("could not create target directory");
+        let filepath = folder.join(format!("{day:02}.dot"));
+        fs::write(&filepath, self.0).expect("could not write dot file");
+        println!("Wrote {}", filepath.display());
+    }
+}
+
+pub fn print_missing_feature(part: u8, feature: &str) {
+    println!(
+        "{}Part {}{}: requires feature {}",
+        ANSI_BOLD, part, ANSI_RESET, feature
+    );
+}
+
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -45,6 +85,32 @@ macro_rules! solution {
             aoc::template::print_result(part_two, &input, 2);
         }
     };
+    // `--<flag>` hands the input to its handler instead of solving, like `dot = to_dot`
+    // returning a `Dot` for `--dot`
+    ($day:expr, $($flag:ident = $handler:path),+ $(,)?) => {
+        fn main() {
+            let input = aoc::template::read_file("inputs", $day);
+            $(
+                if aoc::template::flag_requested(stringify!($flag)) {
+                    aoc::template::FlagOutput::finish($handler(&input), $day);
+                    return;
+                }
+            )+
+            aoc::template::print_result(part_one, &input, 1);
+            aoc::template::print_result(part_two, &input, 2);
+        }
+    };
+    // for days that only build with an optional dependency, gate their code on the same feature
+    ($day:expr, feature = $feature:literal) => {
+        #[cfg(feature = $feature)]
+        aoc::solution!($day);
+
+        #[cfg(not(feature = $feature))]
+        fn main() {
+            aoc::template::print_missing_feature(1, $feature);
+            aoc::template::print_missing_feature(2, $feature);
+        }
+    };
 }
 
 #[must_use]
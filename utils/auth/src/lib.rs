@@ -0,0 +1,323 @@
+/*
+ * Session token handling shared by the utils binaries.
+ * The token is looked up in the environment, `.env` and the user config dir, in that order.
+ */
+use std::{
+    env, fmt,
+    fs::{self, DirBuilder, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use reqwest::{
+    blocking::{Client, Response},
+    header, redirect, StatusCode,
+};
+
+pub const TOKEN_VAR: &str = "TOKEN";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Token(String);
+
+impl Token {
+    pub fn new(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let value = value.strip_prefix("session=").unwrap_or(value);
+
+        if value.is_empty() {
+            return None;
+        }
+
+        Some(Self(value.to_string()))
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        text.replace(&self.0, &self.to_string())
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // only show a prefix of long tokens, so they can still be told apart
+        match self.0.get(..4) {
+            Some(prefix) if self.0.len() >= 16 => write!(f, "{prefix}****"),
+            _ => write!(f, "****"),
+        }
+    }
+}
+
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Token({self})")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Env,
+    DotEnv,
+    File(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Env => write!(f, "${TOKEN_VAR}"),
+            Source::DotEnv => write!(f, ".env"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Missing,
+    Io(PathBuf, io::Error),
+    Http(String),
+    Rejected(StatusCode),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Missing => write!(
+                f,
+                "no session token found, set ${TOKEN_VAR} or run `cargo aoc-login`"
+            ),
+            Error::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            Error::Http(e) => write!(f, "request failed: {e}"),
+            Error::Rejected(status) => write!(f, "server rejected the session token ({status})"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Reads `key` from the environment, falling back to `.env` without loading it.
+pub fn var(key: &str) -> Option<String> {
+    env::var(key).ok().or_else(|| dotenv_var(key))
+}
+
+fn dotenv_var(key: &str) -> Option<String> {
+    dotenvy::dotenv_iter()
+        .ok()?
+        .filter_map(Result::ok)
+        .find_map(|(k, v)| (k == key).then_some(v))
+}
+
+pub fn base_url() -> String {
+    var(BASE_URL_VAR)
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|x| !x.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|x| PathBuf::from(x).join(".config")))
+        .map(|x| x.join("aoc").join("token"))
+}
+
+fn read_token(path: &Path) -> Result<Option<Token>, Error> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Token::new(&contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::Io(path.to_path_buf(), e)),
+    }
+}
+
+pub fn lookup() -> Result<(Token, Source), Error> {
+    if let Some(token) = env::var(TOKEN_VAR).ok().and_then(|x| Token::new(&x)) {
+        return Ok((token, Source::Env));
+    }
+
+    if let Some(token) = dotenv_var(TOKEN_VAR).and_then(|x| Token::new(&x)) {
+        return Ok((token, Source::DotEnv));
+    }
+
+    if let Some(path) = config_path() {
+        if let Some(token) = read_token(&path)? {
+            return Ok((token, Source::File(path)));
+        }
+    }
+
+    Err(Error::Missing)
+}
+
+pub fn token() -> Result<Token, Error> {
+    lookup().map(|(token, _)| token)
+}
+
+/// Writes the token to `path`, readable only by the current user.
+pub fn store(token: &Token, path: &Path) -> Result<(), Error> {
+    let io_error = |e| Error::Io(path.to_path_buf(), e);
+
+    let mut dir_builder = DirBuilder::new();
+    dir_builder.recursive(true);
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+
+        dir_builder.mode(0o700);
+        options.mode(0o600);
+
+        // mode is only applied on creation, tighten files that already exist
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600)).map_err(io_error)?;
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        dir_builder.create(parent).map_err(io_error)?;
+    }
+
+    let mut file = options.open(path).map_err(io_error)?;
+    writeln!(file, "{}", token.expose()).map_err(io_error)
+}
+
+pub fn client(token: &Token) -> Result<Client, Error> {
+    let mut session_header = header::HeaderValue::from_str(&format!("session={}", token.expose()))
+        .map_err(|_| Error::Http(format!("could not build cookie header for {token}")))?;
+    session_header.set_sensitive(true);
+
+    let mut headers = header::HeaderMap::new();
+    headers.insert(header::COOKIE, session_header);
+
+    Client::builder()
+        .default_headers(headers)
+        .user_agent(env!("CARGO_PKG_REPOSITORY"))
+        // logged out requests get redirected, which should not count as success
+        .redirect(redirect::Policy::none())
+        .build()
+        .map_err(|e| Error::Http(token.redact(&e.to_string())))
+}
+
+pub fn get(token: &Token, url: &str) -> Result<Response, Error> {
+    let response = client(token)?
+        .get(url)
+        .send()
+        .map_err(|e| Error::Http(token.redact(&e.to_string())))?;
+
+    match response.status() {
+        status if status.is_success() => Ok(response),
+        status => Err(Error::Rejected(status)),
+    }
+}
+
+pub fn fetch(token: &Token, url: &str) -> Result<String, Error> {
+    get(token, url)?
+        .text()
+        .map_err(|e| Error::Http(token.redact(&e.to_string())))
+}
+
+/// Validates the token by requesting the first input of `year`, which needs a session.
+pub fn check(token: &Token, base_url: &str, year: u32) -> Result<(), Error> {
+    get(token, &format!("{base_url}/{year}/day/1/input")).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        thread,
+    };
+
+    const SECRET: &str = "53616c7465645f5fdeadbeefcafe";
+
+    fn serve_once(status: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request: Vec<String> = BufReader::new(&stream)
+                .lines()
+                .map_while(Result::ok)
+                .take_while(|x| !x.is_empty())
+                .collect();
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            )
+            .unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn test_token_new() {
+        assert_eq!(Token::new(" session=abc\n"), Token::new("abc"));
+        assert_eq!(Token::new("  \n"), None);
+    }
+
+    #[test]
+    fn test_token_masked() {
+        let token = Token::new(SECRET).unwrap();
+        assert_eq!(token.to_string(), "5361****");
+        assert_eq!(format!("{token:?}"), "Token(5361****)");
+        assert_eq!(Token::new("short").unwrap().to_string(), "****");
+        assert_eq!(
+            token.redact(&format!("cookie: session={SECRET}")),
+            "cookie: session=5361****"
+        );
+    }
+
+    #[test]
+    fn test_store() {
+        let path = env::temp_dir()
+            .join(format!("aoc-auth-test-{}", std::process::id()))
+            .join("token");
+        let token = Token::new(SECRET).unwrap();
+
+        store(&token, &path).unwrap();
+        assert_eq!(read_token(&path).unwrap(), Some(token));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_check_accepted() {
+        let (url, handle) = serve_once("200 OK");
+        let token = Token::new(SECRET).unwrap();
+
+        assert!(check(&token, &url, 2023).is_ok());
+
+        let request = handle.join().unwrap();
+        assert_eq!(request[0], "GET /2023/day/1/input HTTP/1.1");
+        assert!(request.contains(&format!("cookie: session={SECRET}")));
+    }
+
+    #[test]
+    fn test_check_rejected() {
+        let (url, handle) = serve_once("400 Bad Request");
+        let token = Token::new(SECRET).unwrap();
+
+        let err = check(&token, &url, 2023).unwrap_err();
+        assert!(matches!(err, Error::Rejected(StatusCode::BAD_REQUEST)));
+        assert!(!err.to_string().contains(SECRET));
+
+        handle.join().unwrap();
+    }
+}
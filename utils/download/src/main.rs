@@ -1,6 +1,4 @@
 use dotenvy::dotenv;
-use reqwest::blocking::Client;
-use reqwest::header;
 use std::{env, fs::OpenOptions, io::Write, process};
 
 pub fn parse_args() -> Result<u8, pico_args::Error> {
@@ -19,30 +17,32 @@ fn main() {
     dotenv().ok();
 
     let day_padded = format!("{day:02}");
-    let token = env::var("TOKEN").expect("$TOKEN is not set");
+    let token = match auth::token() {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
     let year = env::var("YEAR")
         .expect("$YEAR is not set")
         .parse::<u32>()
         .expect("$YEAR must be a number");
 
-    let mut headers = header::HeaderMap::new();
-    let mut session_header = header::HeaderValue::from_str(format!("session={token}").as_str())
-        .expect("Error building cookie header");
-    session_header.set_sensitive(true);
-    headers.insert(header::COOKIE, session_header);
-
-    let client = Client::builder().default_headers(headers).build().unwrap();
-    let res = client
-        .get(format!("https://adventofcode.com/{year}/day/{day}/input"))
-        .send()
-        .unwrap()
-        .text()
-        .unwrap();
+    let url = format!("{}/{year}/day/{day}/input", auth::base_url());
+    let res = match auth::fetch(&token, &url) {
+        Ok(res) => res,
+        Err(e) => {
+            eprintln!("Failed to download input file with token {token}: {e}");
+            process::exit(1);
+        }
+    };
 
     let input_path = format!("data/inputs/{day_padded}.txt");
     let mut file = match OpenOptions::new()
         .write(true)
         .create(true)
+        .truncate(true)
         .open(&input_path)
     {
         Ok(file) => file,
@@ -54,7 +54,7 @@ fn main() {
 
     match file.write_all(res.as_bytes()) {
         Ok(_) => {
-            println!("Downloaded input file \"{}\"", &input_path);
+            println!("Downloaded input file \"{}\"", input_path);
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -0,0 +1,252 @@
+use std::{cmp::Reverse, collections::HashMap, fmt::Write};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    pub completion_day_level: HashMap<u8, HashMap<u8, Star>>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn star_ts(&self, day: u8, part: u8) -> Option<i64> {
+        self.completion_day_level
+            .get(&day)
+            .and_then(|x| x.get(&part))
+            .map(|x| x.get_star_ts)
+    }
+
+    /// Seconds between the puzzle unlocking and the star being earned.
+    pub fn completion_time(&self, year: i32, day: u8, part: u8) -> Option<i64> {
+        self.star_ts(day, part).map(|x| x - unlock_ts(year, day))
+    }
+
+    fn star_row(&self) -> String {
+        (1..=25)
+            .map(|day| match (self.star_ts(day, 1), self.star_ts(day, 2)) {
+                (Some(_), Some(_)) => '*',
+                (Some(_), None) => '+',
+                _ => '.',
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+impl Leaderboard {
+    pub fn year(&self) -> i32 {
+        self.event.parse().unwrap_or_default()
+    }
+
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|m| {
+            (
+                Reverse(m.local_score),
+                Reverse(m.stars),
+                m.last_star_ts,
+                m.id,
+            )
+        });
+        members
+    }
+
+    pub fn latest_day(&self) -> Option<u8> {
+        self.members
+            .values()
+            .flat_map(|m| m.completion_day_level.keys())
+            .max()
+            .copied()
+    }
+
+    pub fn render_summary(&self) -> String {
+        let mut out = String::new();
+        let tens: String = (1..=25)
+            .map(|d| if d < 10 { ' ' } else { tens_digit(d) })
+            .collect();
+        let ones: String = (1..=25).map(|d| char::from(b'0' + d % 10)).collect();
+
+        writeln!(out, "{:>3}  {:>5}  {:>5}  {tens}", "", "", "").unwrap();
+        writeln!(out, "{:>3}  {:>5}  {:>5}  {ones}", "#", "score", "stars").unwrap();
+
+        for (rank, member) in self.ranked().into_iter().enumerate() {
+            writeln!(
+                out,
+                "{:>3}  {:>5}  {:>5}  {}  {}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.star_row(),
+                member.display_name(),
+            )
+            .unwrap();
+        }
+
+        out
+    }
+
+    pub fn render_day(&self, day: u8) -> String {
+        let year = self.year();
+        let mut rows: Vec<_> = self
+            .members
+            .values()
+            .filter_map(|m| {
+                let first = m.completion_time(year, day, 1)?;
+                let second = m.completion_time(year, day, 2);
+                Some((second.unwrap_or(i64::MAX), first, second, m))
+            })
+            .collect();
+        rows.sort_by_key(|&(second, first, _, m)| (second, first, m.id));
+
+        let mut out = String::new();
+        writeln!(out, "Day {day}").unwrap();
+        writeln!(out, "{:>12}  {:>12}", "part 1", "part 2").unwrap();
+
+        for (_, first, second, member) in rows {
+            writeln!(
+                out,
+                "{:>12}  {:>12}  {}",
+                format_duration(first),
+                second
+                    .map(format_duration)
+                    .unwrap_or_else(|| "-".to_string()),
+                member.display_name()
+            )
+            .unwrap();
+        }
+
+        out
+    }
+}
+
+fn tens_digit(day: u8) -> char {
+    char::from(b'0' + day / 10)
+}
+
+pub fn format_duration(seconds: i64) -> String {
+    let (days, rest) = (seconds / 86400, seconds % 86400);
+    let (h, m, s) = (rest / 3600, rest % 3600 / 60, rest % 60);
+
+    if days > 0 {
+        format!("{days}d {h:02}:{m:02}:{s:02}")
+    } else {
+        format!("{h:02}:{m:02}:{s:02}")
+    }
+}
+
+// days since unix epoch for a proleptic gregorian date
+// https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+pub fn unlock_ts(year: i32, day: u8) -> i64 {
+    days_from_civil(year, 12, day as u32) * 86400 + 5 * 3600
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> Leaderboard {
+        serde_json::from_str(include_str!("../fixtures/2023.json")).unwrap()
+    }
+
+    #[test]
+    fn test_unlock_ts() {
+        assert_eq!(unlock_ts(2023, 1), 1701406800);
+        assert_eq!(unlock_ts(2023, 25), 1701406800 + 24 * 86400);
+        assert_eq!(unlock_ts(2024, 1), 1733029200);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(61), "00:01:01");
+        assert_eq!(format_duration(90061), "1d 01:01:01");
+    }
+
+    #[test]
+    fn test_ranked() {
+        let leaderboard = fixture();
+        let names: Vec<_> = leaderboard
+            .ranked()
+            .into_iter()
+            .map(Member::display_name)
+            .collect();
+        assert_eq!(
+            names,
+            ["Ada", "Grace", "(anonymous user #303)", "Linus"].map(String::from)
+        );
+        assert_eq!(leaderboard.latest_day(), Some(3));
+    }
+
+    #[test]
+    fn test_render_summary() {
+        let expected = [
+            "                            1111111111222222",
+            "  #  score  stars  1234567890123456789012345",
+            "  1     23      5  **+......................  Ada",
+            "  2     21      4  **.......................  Grace",
+            "  3      6      2  +.+......................  (anonymous user #303)",
+            "  4      0      0  .........................  Linus",
+        ];
+        assert_eq!(
+            fixture().render_summary().lines().collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_render_day() {
+        let expected = [
+            "Day 3",
+            "      part 1        part 2",
+            "    01:00:00             -  Ada",
+            " 1d 01:01:01             -  (anonymous user #303)",
+        ];
+        assert_eq!(
+            fixture().render_day(3).lines().collect::<Vec<_>>(),
+            expected
+        );
+
+        let expected = [
+            "Day 1",
+            "      part 1        part 2",
+            "    00:03:20      00:06:40  Grace",
+            "    00:05:00      00:10:00  Ada",
+            "    01:23:20             -  (anonymous user #303)",
+        ];
+        assert_eq!(
+            fixture().render_day(1).lines().collect::<Vec<_>>(),
+            expected
+        );
+    }
+}
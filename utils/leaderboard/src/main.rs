@@ -0,0 +1,118 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, SystemTime},
+};
+
+mod leaderboard;
+
+use leaderboard::Leaderboard;
+
+// private leaderboard api asks to not be polled more often than this
+const MIN_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+struct Args {
+    id: Option<String>,
+    day: Option<u8>,
+    year: Option<u32>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        id: args.opt_value_from_str("--id")?,
+        day: args.opt_value_from_str("--day")?,
+        year: args.opt_value_from_str("--year")?,
+    })
+}
+
+fn cache_age(path: &Path) -> Option<Duration> {
+    let modified = fs::metadata(path).and_then(|x| x.modified()).ok()?;
+    SystemTime::now().duration_since(modified).ok()
+}
+
+fn fetch(year: u32, id: &str) -> Result<String, auth::Error> {
+    let token = auth::token()?;
+    let url = format!(
+        "{}/{year}/leaderboard/private/view/{id}.json",
+        auth::base_url()
+    );
+    auth::fetch(&token, &url)
+}
+
+fn load(year: u32, id: &str, cache: &PathBuf) -> String {
+    let age = cache_age(cache);
+
+    if let Some(age) = age.filter(|x| *x < MIN_REFRESH) {
+        println!("Using cached leaderboard ({}m old)", age.as_secs() / 60);
+        return fs::read_to_string(cache).expect("could not read cached leaderboard");
+    }
+
+    match fetch(year, id) {
+        Ok(json) => {
+            if let Err(e) =
+                fs::create_dir_all(cache.parent().unwrap()).and_then(|_| fs::write(cache, &json))
+            {
+                eprintln!("Failed to cache leaderboard: {e}");
+            }
+            json
+        }
+        Err(e) if age.is_some() => {
+            eprintln!("Failed to fetch leaderboard, using stale cache: {e}");
+            fs::read_to_string(cache).expect("could not read cached leaderboard")
+        }
+        Err(e) => {
+            eprintln!("Failed to fetch leaderboard: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}. example: `cargo leaderboard --id 123456 --day 7`");
+            process::exit(1);
+        }
+    };
+
+    let year = match args
+        .year
+        .or_else(|| auth::var("YEAR").and_then(|x| x.parse().ok()))
+    {
+        Some(year) => year,
+        None => {
+            eprintln!("$YEAR must be set to a number or passed with `--year`");
+            process::exit(1);
+        }
+    };
+
+    let id = match args.id.or_else(|| auth::var("LEADERBOARD")) {
+        Some(id) => id,
+        None => {
+            eprintln!("$LEADERBOARD must be set or passed with `--id`");
+            process::exit(1);
+        }
+    };
+
+    let cache = PathBuf::from(format!("data/leaderboard/{year}-{id}.json"));
+    let json = load(year, &id, &cache);
+
+    let leaderboard: Leaderboard = match serde_json::from_str(&json) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Failed to parse leaderboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!();
+    print!("{}", leaderboard.render_summary());
+
+    if let Some(day) = args.day.or_else(|| leaderboard.latest_day()) {
+        println!();
+        print!("{}", leaderboard.render_day(day));
+    }
+}
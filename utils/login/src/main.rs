@@ -0,0 +1,96 @@
+use std::{io, process};
+
+use auth::Token;
+
+struct Args {
+    check: bool,
+    year: Option<u32>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        check: args.contains("--check"),
+        year: args.opt_value_from_str("--year")?,
+    })
+}
+
+fn year(args: &Args) -> u32 {
+    match args
+        .year
+        .or_else(|| auth::var("YEAR").and_then(|x| x.parse().ok()))
+    {
+        Some(year) => year,
+        None => {
+            eprintln!("$YEAR must be set to a number or passed with `--year`");
+            process::exit(1);
+        }
+    }
+}
+
+fn check(args: &Args) {
+    let (token, source) = match auth::lookup() {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let base_url = auth::base_url();
+    match auth::check(&token, &base_url, year(args)) {
+        Ok(()) => println!("Token {token} from {source} is valid for {base_url}"),
+        Err(e) => {
+            eprintln!("Token {token} from {source} is not valid for {base_url}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn store() {
+    let path = match auth::config_path() {
+        Some(path) => path,
+        None => {
+            eprintln!("Could not determine config directory, set $XDG_CONFIG_HOME or $HOME");
+            process::exit(1);
+        }
+    };
+
+    println!("Paste the `session` cookie from adventofcode.com:");
+    let mut line = String::new();
+    let token = match io::stdin()
+        .read_line(&mut line)
+        .ok()
+        .and_then(|_| Token::new(&line))
+    {
+        Some(token) => token,
+        None => {
+            eprintln!("No token given");
+            process::exit(1);
+        }
+    };
+
+    match auth::store(&token, &path) {
+        Ok(()) => println!("Stored token {token} in \"{}\"", path.display()),
+        Err(e) => {
+            eprintln!("Failed to store token: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}. example: `cargo aoc-login --check --year 2023`");
+            process::exit(1);
+        }
+    };
+
+    if args.check {
+        check(&args);
+    } else {
+        store();
+    }
+}
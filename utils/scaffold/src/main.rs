@@ -42,7 +42,11 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
 fn main() {
@@ -70,7 +74,7 @@ fn main() {
 
     match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
         Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path);
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -80,7 +84,7 @@ fn main() {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path);
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -90,7 +94,7 @@ fn main() {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path);
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -99,5 +103,5 @@ fn main() {
     }
 
     println!("---");
-    println!("Type `cargo solve {}` to run your solution.", &day_padded);
+    println!("Type `cargo solve {}` to run your solution.", day_padded);
 }
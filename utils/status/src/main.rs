@@ -0,0 +1,494 @@
+/*
+ * Overview of which days are solved.
+ * Results are recorded with `cargo status --run` and read back from the cache otherwise.
+ * A part only earns its star if it matches the answer in `data/answers/<day>.txt`.
+ */
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+const CACHE_FILE: &str = "status.tsv";
+const CELL_WIDTH: usize = 21;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    /// no answer recorded for the part
+    Unknown,
+    Correct,
+    Wrong,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    NotRun,
+    Unsolved,
+    Solved { answer: String, elapsed: String },
+}
+
+impl Part {
+    fn encode(&self) -> String {
+        match self {
+            Part::NotRun => "-".to_string(),
+            Part::Unsolved => "unsolved".to_string(),
+            Part::Solved { answer, elapsed } => format!("{elapsed} {answer}"),
+        }
+    }
+
+    fn decode(s: &str) -> Self {
+        match s {
+            "-" => Part::NotRun,
+            "unsolved" => Part::Unsolved,
+            solved => {
+                let (elapsed, answer) = solved.split_once(' ').unwrap_or((solved, ""));
+                Part::Solved {
+                    answer: answer.to_string(),
+                    elapsed: elapsed.to_string(),
+                }
+            }
+        }
+    }
+
+    fn verdict(&self, expected: Option<&str>) -> Verdict {
+        match (self, expected) {
+            (Part::Solved { answer, .. }, Some(expected)) if answer == expected => Verdict::Correct,
+            (_, None) => Verdict::Unknown,
+            _ => Verdict::Wrong,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Examples {
+    NotRun,
+    Passed,
+    Failed,
+}
+
+impl Examples {
+    fn encode(&self) -> &str {
+        match self {
+            Examples::NotRun => "-",
+            Examples::Passed => "pass",
+            Examples::Failed => "FAIL",
+        }
+    }
+
+    fn decode(s: &str) -> Self {
+        match s {
+            "pass" => Examples::Passed,
+            "FAIL" => Examples::Failed,
+            _ => Examples::NotRun,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Record {
+    examples: Examples,
+    parts: [Part; 2],
+}
+
+impl Record {
+    fn verdicts(&self, answers: &Answers) -> [Verdict; 2] {
+        [0, 1].map(|i| self.parts[i].verdict(answers[i].as_deref()))
+    }
+
+    fn stars(&self, answers: &Answers) -> usize {
+        self.verdicts(answers)
+            .iter()
+            .filter(|&&x| x == Verdict::Correct)
+            .count()
+    }
+
+    /// Parts that printed an answer nothing can be compared against.
+    fn unverified(&self, answers: &Answers) -> usize {
+        self.parts
+            .iter()
+            .zip(self.verdicts(answers))
+            .filter(|(p, v)| matches!(p, Part::Solved { .. }) && *v == Verdict::Unknown)
+            .count()
+    }
+}
+
+/// Known answers of both parts, one per line in `data/answers/<day>.txt`.
+type Answers = [Option<String>; 2];
+
+fn parse_answers(s: &str) -> Answers {
+    let mut lines = s.lines().map(str::trim);
+    [(); 2].map(|_| lines.next().filter(|x| !x.is_empty()).map(str::to_string))
+}
+
+fn read_answers(day: u8) -> Answers {
+    fs::read_to_string(format!("data/answers/{day:02}.txt"))
+        .map(|x| parse_answers(&x))
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Default)]
+struct Files {
+    solution: bool,
+    example: bool,
+    input: bool,
+}
+
+fn non_empty(path: &str) -> bool {
+    fs::metadata(path).map(|x| x.len() > 0).unwrap_or(false)
+}
+
+fn scan(day: u8) -> Files {
+    let padded = format!("{day:02}");
+    Files {
+        solution: Path::new(&format!("src/bin/{padded}.rs")).exists(),
+        example: [
+            format!("data/examples/{padded}.txt"),
+            format!("data/examples/{padded}-1.txt"),
+        ]
+        .iter()
+        .any(|x| non_empty(x)),
+        input: non_empty(&format!("data/inputs/{padded}.txt")),
+    }
+}
+
+fn cache_path() -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target"))
+        .join(CACHE_FILE)
+}
+
+fn parse_cache(cache: &str) -> HashMap<u8, Record> {
+    cache
+        .lines()
+        .filter_map(|line| {
+            let [day, examples, first, second] =
+                line.split('\t').collect::<Vec<_>>().try_into().ok()?;
+            let record = Record {
+                examples: Examples::decode(examples),
+                parts: [Part::decode(first), Part::decode(second)],
+            };
+            Some((day.parse().ok()?, record))
+        })
+        .collect()
+}
+
+fn write_cache(records: &HashMap<u8, Record>) -> String {
+    let mut days: Vec<_> = records.keys().copied().collect();
+    days.sort();
+
+    let mut out = String::new();
+    for day in days {
+        let r = &records[&day];
+        writeln!(
+            out,
+            "{day:02}\t{}\t{}\t{}",
+            r.examples.encode(),
+            r.parts[0].encode(),
+            r.parts[1].encode()
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|x| x.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+// parses lines printed by `aoc::template::print_result`
+fn parse_output(stdout: &str) -> [Part; 2] {
+    let mut parts = [Part::NotRun, Part::NotRun];
+
+    for line in strip_ansi(stdout).lines() {
+        let Some((part, result)) = line.strip_prefix("Part ").and_then(|x| x.split_once(": "))
+        else {
+            continue;
+        };
+        let index = match part {
+            "1" => 0,
+            "2" => 1,
+            _ => continue,
+        };
+
+        parts[index] = match result.rsplit_once("(elapsed: ") {
+            _ if result == "not solved." => Part::Unsolved,
+            _ if result.starts_with("requires feature ") => Part::NotRun,
+            Some((answer, elapsed)) => Part::Solved {
+                answer: answer.trim().to_string(),
+                elapsed: elapsed.trim_end_matches(')').to_string(),
+            },
+            None => Part::Solved {
+                answer: result.trim().to_string(),
+                elapsed: "?".to_string(),
+            },
+        };
+    }
+
+    parts
+}
+
+fn cargo(args: &[&str]) -> Option<process::Output> {
+    Command::new("cargo").args(args).output().ok()
+}
+
+fn run(day: u8, files: &Files) -> Record {
+    let padded = format!("{day:02}");
+    eprintln!("Running day {padded}...");
+
+    let examples = if files.example {
+        match cargo(&["test", "--release", "--quiet", "--bin", &padded]) {
+            Some(output) if output.status.success() => Examples::Passed,
+            _ => Examples::Failed,
+        }
+    } else {
+        Examples::NotRun
+    };
+
+    let parts = if files.input {
+        cargo(&["run", "--release", "--quiet", "--bin", &padded])
+            .map(|x| parse_output(&String::from_utf8_lossy(&x.stdout)))
+            .unwrap_or([Part::Unsolved, Part::Unsolved])
+    } else {
+        [Part::NotRun, Part::NotRun]
+    };
+
+    Record { examples, parts }
+}
+
+fn cell(day: u8, files: &Files, record: Option<&Record>, answers: &Answers) -> [String; 2] {
+    if !files.solution {
+        return [format!("{day:02}"), String::new()];
+    }
+
+    let stars = match record.map_or(0, |r| r.stars(answers)) {
+        2 => "**",
+        1 => "* ",
+        _ => "  ",
+    };
+    let examples = match (files.example, record) {
+        (false, _) => "no example".to_string(),
+        (true, Some(r)) => format!("ex: {}", r.examples.encode()),
+        (true, None) => "ex: -".to_string(),
+    };
+    let times = match (files.input, record) {
+        (false, _) => "no input".to_string(),
+        (true, Some(r)) => {
+            let time = |p: &Part, v: Verdict| match p {
+                Part::NotRun => "-".to_string(),
+                Part::Unsolved => "x".to_string(),
+                Part::Solved { .. } if v == Verdict::Wrong => "wrong".to_string(),
+                Part::Solved { elapsed, .. } => elapsed.clone(),
+            };
+            let [first, second] = r.verdicts(answers);
+            format!(
+                "{:>9} {:>9}",
+                time(&r.parts[0], first),
+                time(&r.parts[1], second)
+            )
+        }
+        (true, None) => format!("{:>9} {:>9}", "-", "-"),
+    };
+
+    [format!("{day:02} {stars} {examples:>14}"), times]
+}
+
+fn render(days: &[(Files, Option<Record>, Answers)]) -> String {
+    let border = format!(
+        "+{}\n",
+        format!("{}+", "-".repeat(CELL_WIDTH + 2)).repeat(5)
+    );
+    let mut out = border.clone();
+
+    for (row, chunk) in days.chunks(5).enumerate() {
+        let cells: Vec<_> = chunk
+            .iter()
+            .enumerate()
+            .map(|(i, (files, record, answers))| {
+                cell((row * 5 + i + 1) as u8, files, record.as_ref(), answers)
+            })
+            .collect();
+
+        for line in 0..2 {
+            out.push('|');
+            for c in cells.iter() {
+                write!(out, " {:<CELL_WIDTH$} |", c[line]).unwrap();
+            }
+            out.push('\n');
+        }
+        out.push_str(&border);
+    }
+
+    let (stars, unverified) = days
+        .iter()
+        .filter_map(|(_, r, answers)| Some((r.as_ref()?, answers)))
+        .fold((0, 0), |(stars, unverified), (r, answers)| {
+            (stars + r.stars(answers), unverified + r.unverified(answers))
+        });
+    write!(out, "Stars: {stars}/50").unwrap();
+    if unverified > 0 {
+        write!(
+            out,
+            ", {unverified} more ran without an answer in data/answers/"
+        )
+        .unwrap();
+    }
+    out.push('\n');
+
+    out
+}
+
+struct Args {
+    run: bool,
+    day: Option<u8>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        run: args.contains("--run"),
+        day: args.opt_value_from_str("--day")?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}. example: `cargo status --run --day 7`");
+            process::exit(1);
+        }
+    };
+
+    let cache = cache_path();
+    let mut records = fs::read_to_string(&cache)
+        .map(|x| parse_cache(&x))
+        .unwrap_or_default();
+
+    let files: Vec<_> = (1..=25).map(scan).collect();
+
+    if args.run {
+        for (day, f) in (1..=25).zip(files.iter()) {
+            if f.solution && args.day.is_none_or(|x| x == day) {
+                records.insert(day, run(day, f));
+            }
+        }
+
+        if let Err(e) = fs::create_dir_all(cache.parent().unwrap())
+            .and_then(|_| fs::write(&cache, write_cache(&records)))
+        {
+            eprintln!("Failed to write status cache: {e}");
+        }
+    }
+
+    let days: Vec<_> = files
+        .into_iter()
+        .zip(1..=25)
+        .map(|(f, day)| (f, records.remove(&day), read_answers(day)))
+        .collect();
+
+    print!("{}", render(&days));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solved(answer: &str, elapsed: &str) -> Part {
+        Part::Solved {
+            answer: answer.to_string(),
+            elapsed: elapsed.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let stdout = "\x1b[1mPart 1\x1b[0m: 142 \x1b[3m(elapsed: 1.23ms)\x1b[0m\n\
+                      \x1b[1mPart 2\x1b[0m: not solved.\n";
+        assert_eq!(
+            parse_output(stdout),
+            [solved("142", "1.23ms"), Part::Unsolved]
+        );
+        assert_eq!(parse_output(""), [Part::NotRun, Part::NotRun]);
+
+        let stdout = "\x1b[1mPart 1\x1b[0m: requires feature z3\n";
+        assert_eq!(parse_output(stdout), [Part::NotRun, Part::NotRun]);
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let records = HashMap::from([
+            (
+                3,
+                Record {
+                    examples: Examples::Passed,
+                    parts: [solved("4361", "12.50µs"), Part::Unsolved],
+                },
+            ),
+            (
+                12,
+                Record {
+                    examples: Examples::Failed,
+                    parts: [Part::NotRun, Part::NotRun],
+                },
+            ),
+        ]);
+        let cache = write_cache(&records);
+        assert_eq!(cache, "03\tpass\t12.50µs 4361\tunsolved\n12\tFAIL\t-\t-\n");
+        assert_eq!(parse_cache(&cache), records);
+    }
+
+    #[test]
+    fn test_cell() {
+        let files = Files {
+            solution: true,
+            example: true,
+            input: true,
+        };
+        let record = Record {
+            examples: Examples::Passed,
+            parts: [solved("142", "1.23ms"), Part::Unsolved],
+        };
+        let answers = parse_answers("142\n281\n");
+        assert_eq!(
+            cell(7, &files, Some(&record), &answers),
+            ["07 *        ex: pass", "   1.23ms         x"].map(String::from)
+        );
+        assert_eq!(
+            cell(7, &files, Some(&record), &parse_answers("143\n")),
+            ["07          ex: pass", "    wrong         x"].map(String::from)
+        );
+        assert_eq!(
+            cell(8, &Files::default(), None, &Answers::default()),
+            ["08", ""].map(String::from)
+        );
+    }
+
+    #[test]
+    fn test_stars() {
+        let record = Record {
+            examples: Examples::Passed,
+            parts: [solved("142", "1.23ms"), solved("281", "2.00ms")],
+        };
+        assert_eq!(record.stars(&parse_answers("142\n281")), 2);
+        assert_eq!(record.stars(&parse_answers("142\n280")), 1);
+
+        // printing something is not enough without a known answer
+        let answers = parse_answers("142\n");
+        assert_eq!(record.stars(&answers), 1);
+        assert_eq!(record.unverified(&answers), 1);
+        assert_eq!(record.unverified(&Answers::default()), 2);
+    }
+}